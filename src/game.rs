@@ -3,16 +3,18 @@ use bevy_asset_loader::prelude::*;
 use bevy_trauma_shake::TraumaPlugin;
 
 use self::{
-    actions::SvarogActionsPlugin, ai::SvarogAIPlugin, camera::SvarogCameraPlugin, feel::SvarogFeelPlugin, grid::SvarogGridPlugin, history::SvarogHistoryPlugin, inventory::SvarogInventoryPlugin, loading::SvarogLoadingPlugin, magic::SvarogMagicPlugin, music::SvarogMusicPlugin, player::SvarogPlayerPlugin, procgen::SvarogProcgenPlugin, turns::SvarogTurnPlugin, ui::SvarogUIPlugin, window::SvarogWindowPlugins
+    actions::SvarogActionsPlugin, ai::{SvarogAIDebugPlugin, SvarogAIPlugin}, camera::SvarogCameraPlugin, explorer::SvarogExplorerPlugin, feel::SvarogFeelPlugin, grid::SvarogGridPlugin, history::{SvarogHistoryPlugin, TextCatalog}, inventory::{ItemCatalog, SvarogInventoryPlugin}, loading::SvarogLoadingPlugin, magic::SvarogMagicPlugin, mobs::MonsterCatalog, music::SvarogMusicPlugin, navigation::SvarogNavigationPlugin, noise::SvarogNoisePlugin, outcomes::SvarogOutcomesPlugin, player::SvarogPlayerPlugin, procgen::{LevelCatalog, SvarogProcgenPlugin}, replay::SvarogReplayPlugin, save::SvarogSavePlugin, turns::{SvarogTurnDebugPlugin, SvarogTurnPlugin}, ui::SvarogUIPlugin, window::SvarogWindowPlugins
 };
 
 pub mod actions;
 pub mod ai;
 pub mod camera;
 pub mod character;
+pub mod explorer;
 pub mod feel;
 pub mod fov;
 pub mod grid;
+pub mod headless;
 pub mod health;
 pub mod history;
 pub mod inventory;
@@ -21,6 +23,8 @@ pub mod magic;
 pub mod mobs;
 pub mod player;
 pub mod procgen;
+pub mod replay;
+pub mod save;
 pub mod spells;
 pub mod sprite;
 pub mod sprites;
@@ -28,6 +32,10 @@ pub mod turns;
 pub mod ui;
 pub mod window;
 pub mod music;
+pub mod navigation;
+pub mod noise;
+pub mod outcomes;
+pub mod status;
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum GameStates {
@@ -41,6 +49,14 @@ pub enum GameStates {
 pub struct GameAssets {
     #[asset(key = "atlas")]
     pub atlas: Handle<TextureAtlas>,
+    #[asset(path = "hellth.monsters.ron")]
+    pub monsters: Handle<MonsterCatalog>,
+    #[asset(path = "hellth.levels.ron")]
+    pub levels: Handle<LevelCatalog>,
+    #[asset(path = "hellth.items.ron")]
+    pub items: Handle<ItemCatalog>,
+    #[asset(path = "hellth.text.ron")]
+    pub text: Handle<TextCatalog>,
 }
 
 #[derive(Event)]
@@ -51,29 +67,56 @@ pub enum CharacterIntent {
 #[derive(Event)]
 pub struct StartGameEvent;
 
-pub struct SvarogGamePlugin;
-
 #[derive(Resource, Default)]
 pub struct DebugFlag(pub bool);
 
-impl Plugin for SvarogGamePlugin {
+// everything the game needs to actually run: grid, turns, actions, AI, procgen.
+// nothing in here should touch a window, imgui, audio or the renderer.
+pub struct SvarogSimulationPlugin;
+
+impl Plugin for SvarogSimulationPlugin {
     fn build(&self, bevy: &mut App) {
         bevy.insert_resource::<DebugFlag>(DebugFlag(false))
-            .add_plugins(SvarogWindowPlugins)
             .add_plugins(SvarogMagicPlugin)
             .add_plugins(SvarogHistoryPlugin)
-            .add_plugins(SvarogLoadingPlugin)
             .add_plugins(SvarogActionsPlugin)
             .add_plugins(SvarogGridPlugin)
             .add_plugins(SvarogFeelPlugin)
             .add_plugins(SvarogProcgenPlugin)
-            .add_plugins(SvarogCameraPlugin)
             .add_plugins(SvarogTurnPlugin)
             .add_plugins(SvarogPlayerPlugin)
             .add_plugins(SvarogAIPlugin)
             .add_plugins(SvarogInventoryPlugin)
+            .add_plugins(SvarogNavigationPlugin)
+            .add_plugins(SvarogNoisePlugin)
+            .add_plugins(SvarogOutcomesPlugin);
+    }
+}
+
+// asset loading, cameras, imgui windows, music and screen shake.
+pub struct SvarogPresentationPlugin;
+
+impl Plugin for SvarogPresentationPlugin {
+    fn build(&self, bevy: &mut App) {
+        bevy.add_plugins(SvarogLoadingPlugin)
+            .add_plugins(SvarogCameraPlugin)
+            .add_plugins(SvarogTurnDebugPlugin)
+            .add_plugins(SvarogAIDebugPlugin)
             .add_plugins(SvarogUIPlugin)
             .add_plugins(SvarogMusicPlugin)
             .add_plugins(TraumaPlugin);
     }
 }
+
+pub struct SvarogGamePlugin;
+
+impl Plugin for SvarogGamePlugin {
+    fn build(&self, bevy: &mut App) {
+        bevy.add_plugins(SvarogWindowPlugins)
+            .add_plugins(SvarogSimulationPlugin)
+            .add_plugins(SvarogReplayPlugin)
+            .add_plugins(SvarogSavePlugin)
+            .add_plugins(SvarogExplorerPlugin)
+            .add_plugins(SvarogPresentationPlugin);
+    }
+}
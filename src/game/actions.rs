@@ -1,22 +1,37 @@
+pub mod action_kind;
+pub mod action_rejection;
 pub mod ai_think_action;
 pub mod break_action;
 pub mod consume_action;
 pub mod death_action;
+pub mod delay_action;
 pub mod descend_action;
+pub mod desintegrate_action;
 pub mod destroy_action;
 pub mod drop_action;
+pub mod dust_action;
+pub mod edge_action;
 pub mod equip_action;
 pub mod flee_action;
 pub mod fly_action;
 pub mod focus_action;
 pub mod hit_action;
 pub mod inflict_action;
+pub mod investigate_action;
+pub mod kite_action;
 pub mod leave_bones_action;
+pub mod lore_action;
 pub mod melee_attack_action;
 pub mod move_action;
 pub mod pickup_action;
+pub mod prison_action;
 pub mod random_walk_action;
+pub mod regalia_action;
+pub mod sacrifice_action;
+pub mod scavenge_action;
+pub mod surround_action;
 pub mod switch_behaviour_action;
+pub mod teleport_action;
 pub mod throw_action;
 pub mod track_action;
 pub mod unequip_action;
@@ -25,23 +40,38 @@ pub mod yell_action;
 pub mod fortune_action;
 pub mod heal_action;
 
+#[cfg(test)]
+pub(crate) mod tests;
+
 use std::collections::VecDeque;
 use std::fmt::Debug;
 
-use super::{character::CharacterStat, music::GameAudioSettings};
+use super::{
+    character::{Character, CharacterStat},
+    health::Health,
+    music::GameAudioSettings,
+    outcomes::send_outcomes,
+    status::StatusEffects,
+    turns::{action_cost, Speed, TurnOrder},
+};
 
 pub use {
-    ai_think_action::a_think, break_action::a_break, 
-    consume_action::a_consume, death_action::a_death, descend_action::a_descend,
-    destroy_action::a_destroy, drop_action::a_drop, equip_action::a_equip, flee_action::a_flee,
-    fly_action::a_fly, focus_action::a_focus, hit_action::a_hit, inflict_action::a_inflict,
-    leave_bones_action::a_leave_bones, melee_attack_action::a_melee, move_action::a_move,
-    pickup_action::a_pickup, random_walk_action::a_random_walk, switch_behaviour_action::a_behave,
-    throw_action::a_throw, track_action::a_track, unequip_action::a_unequip, wait_action::a_wait,
-    yell_action::a_yell, fortune_action::a_fortune, heal_action::a_heal
+    action_kind::ActionKind, action_rejection::{reject, ActionRejection}, ai_think_action::a_think,
+    break_action::a_break, consume_action::a_consume, death_action::a_death, delay_action::a_delay,
+    descend_action::a_descend, desintegrate_action::a_desintegrate, destroy_action::a_destroy,
+    drop_action::a_drop, dust_action::a_dust, edge_action::a_edge, equip_action::a_equip,
+    flee_action::a_flee, fly_action::a_fly, focus_action::a_focus, hit_action::a_hit,
+    inflict_action::a_inflict, investigate_action::a_investigate, kite_action::a_kite,
+    leave_bones_action::a_leave_bones, lore_action::a_lore, melee_attack_action::a_melee,
+    move_action::a_move, pickup_action::a_pickup, prison_action::a_prison,
+    random_walk_action::a_random_walk, regalia_action::a_regalia, sacrifice_action::a_sacrifice,
+    scavenge_action::a_scavenge, surround_action::a_surround, switch_behaviour_action::a_behave,
+    teleport_action::a_teleport, throw_action::a_throw, track_action::a_track,
+    unequip_action::a_unequip, wait_action::a_wait, yell_action::a_yell, fortune_action::a_fortune,
+    heal_action::a_heal
 };
 
-use bevy::prelude::*;
+use bevy::{ecs::system::Command, prelude::*};
 use bevy_kira_audio::{Audio, AudioControl};
 
 pub type AbstractAction = Box<dyn Action>;
@@ -50,13 +80,18 @@ pub type ActionResult = Vec<AbstractAction>;
 
 pub trait Action: Send + Sync + Debug {
     fn get_affiliated_stat(&self) -> CharacterStat;
+    fn kind(&self) -> ActionKind;
+    // checked before anyone pays for the action, and again right before it's done
+    fn validate(&self, _world: &World) -> Result<(), ActionRejection> {
+        Ok(())
+    }
     fn do_action(&self, world: &mut World) -> ActionResult;
 }
 
 #[derive(Event)]
 pub struct ActionEvent(pub AbstractAction);
 
-fn handle_gameplay_action(world: &mut World) {
+pub fn handle_gameplay_action(world: &mut World) {
     let events = if let Some(mut res) = world.get_resource_mut::<Events<ActionEvent>>() {
         res.drain().collect::<Vec<_>>()
     } else {
@@ -65,17 +100,62 @@ fn handle_gameplay_action(world: &mut World) {
 
     let mut reactions = VecDeque::new();
     for ev in events {
-        {
-            reactions.extend(ev.0.do_action(world));
-            while !reactions.is_empty() {
-                let reaction = reactions.pop_front().unwrap();
-                let more_reactions = reaction.do_action(world);
-                reactions.extend(more_reactions);
+        reactions.push_back(ev.0);
+        while let Some(reaction) = reactions.pop_front() {
+            // things might have changed since the action was decided on
+            if let Err(rejection) = reaction.validate(world) {
+                reject(world, reaction.as_ref(), rejection);
+                continue;
             }
+
+            reactions.extend(reaction.do_action(world));
+            send_outcomes(world);
         }
     }
 }
 
+// has whoever is on top of the turn order pay for the action and sends it. actions that can't
+// be done are rejected instead, and cost nothing. returns false if the action didn't go through.
+pub fn submit_action(world: &mut World, action: AbstractAction) -> bool {
+    let Some(entity) = world.resource::<TurnOrder>().peek() else {
+        return false;
+    };
+
+    if let Err(rejection) = action.validate(world) {
+        reject(world, action.as_ref(), rejection);
+        return false;
+    }
+
+    let cost = world
+        .get::<Character>(entity)
+        .map(|character| character.calculate_cost(action.get_affiliated_stat()))
+        .unwrap_or(100);
+    let cost = action_cost(
+        cost,
+        world.get::<Speed>(entity),
+        world.get::<StatusEffects>(entity),
+        world.get::<Health>(entity),
+    );
+
+    let mut turn_order = world.resource_mut::<TurnOrder>();
+    turn_order.pushback(cost);
+    turn_order.tick();
+    world.send_event(ActionEvent(action));
+    for due in world.resource_mut::<TurnOrder>().take_due() {
+        world.send_event(ActionEvent(due));
+    }
+    true
+}
+
+// `submit_action` for systems that only get to decide on the action
+pub struct SubmitAction(pub AbstractAction);
+
+impl Command for SubmitAction {
+    fn apply(self, world: &mut World) {
+        submit_action(world, self.0);
+    }
+}
+
 pub fn play_sfx(name: &str, world: &mut World) {
     if let Some(settings) = world.get_resource::<GameAudioSettings>() {
         if let Some(asset_server) = world.get_resource::<AssetServer>() {
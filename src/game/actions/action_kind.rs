@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{ai::AIStrategy, character::CharacterStat, inventory::Item};
+
+use super::*;
+
+// every action as plain data: this is what gets saved, recorded or sent around,
+// and `into_action` turns it back into something that can be executed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ActionKind {
+    Think { entity: Entity, strategy: AIStrategy },
+    Break { what: Entity },
+    Consume { who: Entity, what: Entity },
+    Death { entity: Entity },
+    // the action comes due `delay` later (see `turns::TURN`)
+    Delay { delay: u64, action: Box<ActionKind> },
+    Descend { who: Entity },
+    Desintegrate { what: Entity },
+    Destroy { what: Entity },
+    Drop { who: Entity, what: Vec<Entity> },
+    Dust { who: Entity },
+    Edge { who: Entity },
+    Equip { who: Entity, what: Entity },
+    Flee { who: Entity, target: Entity },
+    Fly { what: Entity, path: Vec<IVec2>, already_flying: bool },
+    Focus { who: Entity },
+    Fortune { who: Entity, what: Entity },
+    Heal { entity: Entity },
+    Hit { attacker: Entity, target: Entity },
+    Inflict { who: Entity, target: Entity, artifact: Item },
+    Investigate { who: Entity, at: IVec2 },
+    Kite { who: Entity, target: Entity },
+    LeaveBones { stats: Vec<(CharacterStat, i32)>, pos: IVec2 },
+    Lore { who: Entity, stats: Vec<CharacterStat> },
+    Melee { entity: Entity, direction: IVec2 },
+    Move { entity: Entity, direction: IVec2 },
+    Pickup { who: Entity, what: Vec<Entity> },
+    Prison { who: Entity },
+    RandomWalk { who: Entity },
+    Regalia { who: Entity },
+    Sacrifice { who: Entity },
+    Behave { entity: Entity, behaviour: AIStrategy },
+    Throw { who: Entity, what: Entity, wher: IVec2 },
+    Scavenge { who: Entity },
+    Surround { who: Entity, target: Entity },
+    Teleport { who: Entity, wher: IVec2 },
+    Track { who: Entity, target: Entity },
+    Unequip { who: Entity, what: Entity },
+    Wait { who: Entity },
+    Yell { who: Entity },
+}
+
+impl ActionKind {
+    pub fn into_action(self) -> AbstractAction {
+        match self {
+            ActionKind::Think { entity, strategy } => a_think(entity, strategy),
+            ActionKind::Break { what } => a_break(what),
+            ActionKind::Consume { who, what } => a_consume(who, what),
+            ActionKind::Death { entity } => a_death(entity),
+            ActionKind::Delay { delay, action } => a_delay(delay, *action),
+            ActionKind::Descend { who } => a_descend(who),
+            ActionKind::Desintegrate { what } => a_desintegrate(what),
+            ActionKind::Destroy { what } => a_destroy(what),
+            ActionKind::Drop { who, what } => a_drop(who, what),
+            ActionKind::Dust { who } => a_dust(who),
+            ActionKind::Edge { who } => a_edge(who),
+            ActionKind::Equip { who, what } => a_equip(who, what),
+            ActionKind::Flee { who, target } => a_flee(who, target),
+            ActionKind::Fly {
+                what,
+                path,
+                already_flying,
+            } => a_fly(what, path, already_flying),
+            ActionKind::Focus { who } => a_focus(who),
+            ActionKind::Fortune { who, what } => a_fortune(who, what),
+            ActionKind::Heal { entity } => a_heal(entity),
+            ActionKind::Hit { attacker, target } => a_hit(attacker, target),
+            ActionKind::Inflict {
+                who,
+                target,
+                artifact,
+            } => a_inflict(who, target, artifact),
+            ActionKind::Investigate { who, at } => a_investigate(who, at),
+            ActionKind::Kite { who, target } => a_kite(who, target),
+            ActionKind::LeaveBones { stats, pos } => a_leave_bones(stats, pos),
+            ActionKind::Lore { who, stats } => a_lore(who, stats),
+            ActionKind::Melee { entity, direction } => a_melee(entity, direction),
+            ActionKind::Move { entity, direction } => a_move(entity, direction),
+            ActionKind::Pickup { who, what } => a_pickup(who, what),
+            ActionKind::Prison { who } => a_prison(who),
+            ActionKind::RandomWalk { who } => a_random_walk(who),
+            ActionKind::Regalia { who } => a_regalia(who),
+            ActionKind::Sacrifice { who } => a_sacrifice(who),
+            ActionKind::Behave { entity, behaviour } => a_behave(entity, behaviour),
+            ActionKind::Throw { who, what, wher } => a_throw(who, what, wher),
+            ActionKind::Scavenge { who } => a_scavenge(who),
+            ActionKind::Surround { who, target } => a_surround(who, target),
+            ActionKind::Teleport { who, wher } => a_teleport(who, wher),
+            ActionKind::Track { who, target } => a_track(who, target),
+            ActionKind::Unequip { who, what } => a_unequip(who, what),
+            ActionKind::Wait { who } => a_wait(who),
+            ActionKind::Yell { who } => a_yell(who),
+        }
+    }
+
+    // whoever is doing the action, if anyone
+    pub fn actor(&self) -> Option<Entity> {
+        match self {
+            ActionKind::Think { entity, .. }
+            | ActionKind::Heal { entity }
+            | ActionKind::Melee { entity, .. }
+            | ActionKind::Move { entity, .. }
+            | ActionKind::Behave { entity, .. } => Some(*entity),
+
+            ActionKind::Consume { who, .. }
+            | ActionKind::Descend { who }
+            | ActionKind::Drop { who, .. }
+            | ActionKind::Dust { who }
+            | ActionKind::Edge { who }
+            | ActionKind::Equip { who, .. }
+            | ActionKind::Flee { who, .. }
+            | ActionKind::Focus { who }
+            | ActionKind::Fortune { who, .. }
+            | ActionKind::Inflict { who, .. }
+            | ActionKind::Investigate { who, .. }
+            | ActionKind::Kite { who, .. }
+            | ActionKind::Lore { who, .. }
+            | ActionKind::Pickup { who, .. }
+            | ActionKind::Prison { who }
+            | ActionKind::RandomWalk { who }
+            | ActionKind::Regalia { who }
+            | ActionKind::Sacrifice { who }
+            | ActionKind::Throw { who, .. }
+            | ActionKind::Scavenge { who }
+            | ActionKind::Surround { who, .. }
+            | ActionKind::Teleport { who, .. }
+            | ActionKind::Track { who, .. }
+            | ActionKind::Unequip { who, .. }
+            | ActionKind::Wait { who }
+            | ActionKind::Yell { who } => Some(*who),
+
+            ActionKind::Hit { attacker, .. } => Some(*attacker),
+
+            // these happen to someone or something, or to the world itself
+            ActionKind::Break { .. }
+            | ActionKind::Death { .. }
+            | ActionKind::Delay { .. }
+            | ActionKind::Desintegrate { .. }
+            | ActionKind::Destroy { .. }
+            | ActionKind::Fly { .. }
+            | ActionKind::LeaveBones { .. } => None,
+        }
+    }
+
+    // rewrites every entity this action refers to, e.g. into save-file indices and back
+    pub fn map_entities(&mut self, map: &mut impl FnMut(Entity) -> Entity) {
+        match self {
+            ActionKind::Think { entity, .. }
+            | ActionKind::Death { entity }
+            | ActionKind::Heal { entity }
+            | ActionKind::Melee { entity, .. }
+            | ActionKind::Move { entity, .. }
+            | ActionKind::Behave { entity, .. } => *entity = map(*entity),
+
+            ActionKind::Break { what }
+            | ActionKind::Desintegrate { what }
+            | ActionKind::Destroy { what }
+            | ActionKind::Fly { what, .. } => *what = map(*what),
+
+            ActionKind::Focus { who }
+            | ActionKind::Descend { who }
+            | ActionKind::Dust { who }
+            | ActionKind::Edge { who }
+            | ActionKind::Lore { who, .. }
+            | ActionKind::Prison { who }
+            | ActionKind::Regalia { who }
+            | ActionKind::Sacrifice { who }
+            | ActionKind::Teleport { who, .. }
+            | ActionKind::Investigate { who, .. }
+            | ActionKind::Scavenge { who }
+            | ActionKind::RandomWalk { who }
+            | ActionKind::Wait { who }
+            | ActionKind::Yell { who } => *who = map(*who),
+
+            ActionKind::Consume { who, what }
+            | ActionKind::Equip { who, what }
+            | ActionKind::Unequip { who, what }
+            | ActionKind::Fortune { who, what }
+            | ActionKind::Throw { who, what, .. } => {
+                *who = map(*who);
+                *what = map(*what);
+            }
+
+            ActionKind::Drop { who, what } | ActionKind::Pickup { who, what } => {
+                *who = map(*who);
+                for item in what.iter_mut() {
+                    *item = map(*item);
+                }
+            }
+
+            ActionKind::Flee { who, target }
+            | ActionKind::Kite { who, target }
+            | ActionKind::Surround { who, target }
+            | ActionKind::Track { who, target }
+            | ActionKind::Inflict { who, target, .. } => {
+                *who = map(*who);
+                *target = map(*target);
+            }
+
+            ActionKind::Hit { attacker, target } => {
+                *attacker = map(*attacker);
+                *target = map(*target);
+            }
+
+            ActionKind::Delay { action, .. } => action.map_entities(map),
+
+            ActionKind::LeaveBones { .. } => {}
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    ai::PendingActions,
+    history::{args, HistoryLog},
+    procgen::PlayerMarker,
+};
+
+use super::Action;
+
+// why an action can't be done. rejected actions cost no time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionRejection {
+    // there's a wall in the way
+    Blocked,
+    // two things are equipped already
+    HandsFull,
+    OutOfRange,
+    // equipped items have to be unequipped before they're thrown
+    Equipped,
+    // nine things are carried already
+    NoSpace,
+    // whoever or whatever the action is about isn't around anymore
+    Gone,
+    // offerings can only be made standing on an altar
+    NoAltar,
+    // and only by someone who has something to offer
+    NoOffering,
+}
+
+impl ActionRejection {
+    pub fn key(&self) -> &'static str {
+        match self {
+            ActionRejection::Blocked => "rejected.blocked",
+            ActionRejection::HandsFull => "rejected.hands_full",
+            ActionRejection::OutOfRange => "rejected.out_of_range",
+            ActionRejection::Equipped => "throw.equipped",
+            ActionRejection::NoSpace => "item.no_space",
+            ActionRejection::Gone => "rejected.gone",
+            ActionRejection::NoAltar => "rejected.no_altar",
+            ActionRejection::NoOffering => "rejected.no_offering",
+        }
+    }
+}
+
+// whoever wanted the action drops the rest of their plan, so that they think again. only the
+// player gets told why.
+pub fn reject(world: &mut World, action: &dyn Action, rejection: ActionRejection) {
+    let Some(who) = action.kind().actor() else {
+        return;
+    };
+
+    if let Some(mut plan) = world.get_mut::<PendingActions>(who) {
+        plan.0.clear();
+    }
+
+    if world.get::<PlayerMarker>(who).is_some() {
+        world.resource_mut::<HistoryLog>().say(rejection.key(), args());
+    }
+}
@@ -1,17 +1,21 @@
 use bevy::prelude::*;
 
-use crate::game::ai::{get_player, AbstractAIBehaviour, PendingActions};
+use crate::game::ai::{
+    awareness::{perceive, think_without_sight, Awareness},
+    factions::{adjacent_rival, share_awareness},
+    get_player, AIStrategy, AbstractAIBehaviour, PendingActions,
+};
 
 use super::*;
 
 #[derive(Debug)]
 pub struct AIThinkAction {
     pub entity: Entity,
-    pub behaviour: AbstractAIBehaviour,
+    pub strategy: AIStrategy,
 }
 
-pub fn a_think(entity: Entity, behaviour: AbstractAIBehaviour) -> AbstractAction {
-    Box::new(AIThinkAction { entity, behaviour })
+pub fn a_think(entity: Entity, strategy: AIStrategy) -> AbstractAction {
+    Box::new(AIThinkAction { entity, strategy })
 }
 
 impl Action for AIThinkAction {
@@ -19,17 +23,36 @@ impl Action for AIThinkAction {
         CharacterStat::INT
     }
 
-    fn do_action(&self, world: &mut World) -> ActionResult {
-        if let Some(_) = get_player(world) {
+    fn kind(&self) -> ActionKind {
+        ActionKind::Think {
+            entity: self.entity,
+            strategy: self.strategy,
+        }
+    }
 
-            let planned_actions = self.behaviour.do_thinking(self.entity, world);
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        if get_player(world).is_some() {
+            // a rival faction within reach gets dealt with first, and only those who know
+            // where the player is get to follow their strategy
+            let planned_actions = if let Some(direction) = adjacent_rival(world, self.entity) {
+                vec![a_melee(self.entity, direction)]
+            } else {
+                match perceive(world, self.entity) {
+                    None | Some(Awareness::Hunting { .. }) => {
+                        share_awareness(world, self.entity);
+                        let behaviour: AbstractAIBehaviour = self.strategy.into();
+                        behaviour.do_thinking(self.entity, world)
+                    }
+                    Some(awareness) => think_without_sight(world, self.entity, awareness),
+                }
+            };
             if let Some(mut plan) = world.get_mut::<PendingActions>(self.entity) {
                 plan.0 = VecDeque::from_iter(planned_actions);
             }
 
             vec![]
         } else {
-            vec![a_wait()]
+            vec![a_wait(self.entity)]
         }
     }
 }
@@ -4,11 +4,11 @@ use crate::game::{
     character::{Character, CharacterStat},
     grid::{Grid, WorldData, WorldEntity},
     health::Health,
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::{Item, ItemType},
 };
 
-use super::{a_death, AbstractAction, Action, ActionResult};
+use super::{a_death, AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct BreakAction {
@@ -24,6 +24,10 @@ impl Action for BreakAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Break { what: self.what }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             Query<(&mut WorldEntity, &mut Transform, Option<&mut Character>)>,
@@ -38,24 +42,28 @@ impl Action for BreakAction {
             read_system_state.get_mut(world);
 
         let Ok((item, _)) = items.get_mut(self.what) else {
-            log.add("ERR: No item found.");
+            log.say("error.no_item", args());
             return vec![];
         };
 
         let Ok((item_world_entity, _, _)) = transforms.get_mut(self.what) else {
-            log.add("ERR: No world item found.");
+            log.say("error.no_world_item", args());
             return vec![];
         };
 
         let mut result = vec![];
+        let at = item_world_entity.position;
+        let spell = item.spell.map(|spell| (spell, world_data.blocking.get(&at).copied()));
 
         let breaks = match item.item_type {
             ItemType::Artifact => {
-                log.add("The thrown artifact breaks on impact!");
+                log.say("throw.artifact_breaks", args());
                 if let Some(e) = world_data.blocking.get(&item_world_entity.position) {
                     if let Ok((hit_entity, _, Some(mut hit_char))) = transforms.get_mut(*e) {
-                        let mut message: Vec<String> =
-                            vec![format!("The broken artifact affects {}.", hit_entity.name)];
+                        let mut message: Vec<String> = vec![log.text(
+                            "throw.artifact_affects",
+                            args().with("target", &hit_entity.name),
+                        )];
 
                         if let Ok(mut health) = healths.get_mut(*e) {
                             for (pos, (stat, val)) in item.equip_stat_changes.iter().enumerate() {
@@ -68,12 +76,12 @@ impl Action for BreakAction {
                                             *e += 1;
                                         }
 
-                                        message.push(format!(
-                                            "{} has its {} {} by {}.",
-                                            hit_entity.name,
-                                            format!("{:?}", effect).to_uppercase(),
-                                            if val > 0 { "raised" } else { "lowered" },
-                                            val.abs()
+                                        message.push(log.text(
+                                            if val > 0 { "stat.raised" } else { "stat.lowered" },
+                                            args()
+                                                .subject(&hit_entity)
+                                                .with("stat", format!("{:?}", effect).to_uppercase())
+                                                .with("amount", val.abs()),
                                         ));
                                     }
                                 }
@@ -83,7 +91,10 @@ impl Action for BreakAction {
                         log.add("");
                     }
                 } else {
-                    log.add(&format!("Err: NO HIT at {:?}!", item_world_entity.position));
+                    log.say(
+                        "error.no_hit",
+                        args().with("position", format!("{:?}", item_world_entity.position)),
+                    );
                 }
                 true
             }
@@ -99,12 +110,13 @@ impl Action for BreakAction {
                     if item.name.contains("Staff") {
                         damage = 1;
                     }
-                    log.add(&format!(
-                        "The {} hits {} for {} damage.",
-                        format!("{:?}", item.item_type).to_lowercase(),
-                        hit_entity.name,
-                        damage
-                    ));
+                    log.say(
+                        "throw.weapon_hits",
+                        args()
+                            .with("item", format!("{:?}", item.item_type).to_lowercase())
+                            .with("target", &hit_entity.name)
+                            .count(damage as i64),
+                    );
                     log.add("");
 
                     if let Ok(mut health) = healths.get_mut(*e) {
@@ -141,6 +153,9 @@ impl Action for BreakAction {
             }
         }
 
+        if let Some((spell, hit)) = spell {
+            result.extend(spell.cast(world, hit, at));
+        }
         result
     }
 }
@@ -1,16 +1,17 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    actions::{a_destroy, play_sfx},
+    actions::a_destroy,
     character::{Character, CharacterStat},
     grid::WorldEntity,
     health::Health,
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::Item,
     magic::Focus,
+    outcomes::{report, ActionOutcome},
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct ConsumeAction {
@@ -27,6 +28,13 @@ impl Action for ConsumeAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Consume {
+            who: self.who,
+            what: self.what,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             ResMut<HistoryLog>,
@@ -40,14 +48,20 @@ impl Action for ConsumeAction {
             return vec![];
         };
 
+        let spell = item.spell;
+        let mut at = None;
+        let mut enchanted = vec![];
         if let Ok((mut character, world_entity, mut health, mut focus)) =
             world_entity_query.get_mut(self.who)
         {
-            let mut message = vec![format!("{} consumed {}.", world_entity.name, item.name)];
+            at = Some(world_entity.position);
+            let mut message = vec![log.text(
+                "item.consumed",
+                args().subject(world_entity).with("item", &item.name),
+            )];
 
             if focus.0 > 0 && world_entity.is_player {
-                message
-                    .push("You are focused, enchanting deeper reaches of your soul.".to_string());
+                message.push(log.text("item.consumed_focused", args()));
             }
 
             let hp_total = (health.hitpoints.len() - 1) as isize;
@@ -56,7 +70,7 @@ impl Action for ConsumeAction {
                 let pos = hp_total - index as isize - focus.0 as isize;
 
                 if pos < 0 && !already_missed {
-                    message.push("Part of the spell missed.".to_string());
+                    message.push(log.text("item.spell_missed", args()));
                     already_missed = true;
                     continue;
                 }
@@ -64,18 +78,19 @@ impl Action for ConsumeAction {
                 if let Some(hp) = health.hitpoints.get_mut(pos as usize) {
                     for (effect, val) in hp.enchant(*effect_val) {
                         character[effect] += val;
+                        enchanted.push((effect, val));
 
                         {
                             let e = character.counters.entry(effect).or_insert(0);
                             *e += 1;
                         }
 
-                        message.push(format!(
-                            "{} {} {} by {}.",
-                            world_entity.name,
-                            if val > 0 { "raise" } else { "lower" },
-                            format!("{:?}", effect).to_uppercase(),
-                            val.abs()
+                        message.push(log.text(
+                            if val > 0 { "stat.raise" } else { "stat.lower" },
+                            args()
+                                .subject(world_entity)
+                                .with("stat", format!("{:?}", effect).to_uppercase())
+                                .with("amount", val.abs()),
                         ));
                     }
                 }
@@ -86,7 +101,24 @@ impl Action for ConsumeAction {
             log.add(&message.join(" "));
         }
 
-        play_sfx("item_cast", world);
-        vec![a_destroy(self.what)]
+        let consumed = ActionOutcome::ItemConsumed {
+            who: self.who,
+            what: self.what,
+        };
+        report(world, consumed);
+        for (stat, amount) in enchanted {
+            let enchanted = ActionOutcome::HitPointEnchanted {
+                who: self.who,
+                stat,
+                amount,
+            };
+            report(world, enchanted);
+        }
+
+        let mut result = vec![a_destroy(self.what)];
+        if let (Some(spell), Some(at)) = (spell, at) {
+            result.extend(spell.cast(world, Some(self.who), at));
+        }
+        result
     }
 }
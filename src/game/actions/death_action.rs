@@ -3,8 +3,10 @@ use bevy::{ecs::system::SystemState, prelude::*};
 use crate::game::{
     character::Character,
     grid::{WorldData, WorldEntity},
-    history::HistoryLog,
+    history::{args, HistoryLog},
+    inventory::CarriedItems,
     mobs::TheHealer,
+    outcomes::{report, ActionOutcome},
     player::PlayerState,
     turns::{TurnOrder, TurnOrderEntity},
 };
@@ -25,8 +27,13 @@ impl Action for DeathAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Death {
+            entity: self.entity,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let mut sfx = None;
         let (result, is_player) = {
             let mut read_system_state = SystemState::<(
                 ResMut<WorldData>,
@@ -55,7 +62,7 @@ impl Action for DeathAction {
                 entity: self.entity,
             });
 
-            log.add(&format!("{} died.", world_entity.name));
+            log.say("death", args().subject(&world_entity));
             log.add("");
             if !world_entity.is_player {
                 let stats = make_item(character);
@@ -64,7 +71,6 @@ impl Action for DeathAction {
                     *player_state = PlayerState::Ascended;
                 }
 
-                sfx = Some("gameplay_kill");
                 (
                     if !stats.is_empty() {
                         vec![a_leave_bones(stats, world_entity.position)]
@@ -74,12 +80,23 @@ impl Action for DeathAction {
                     false,
                 )
             } else {
-                *player_state = PlayerState::Dead;                
-                sfx = Some("gameplay_death");
+                *player_state = PlayerState::Dead;
                 (vec![], true)
             }
         };
 
+        // whatever a monster carried ends up on the floor where it fell
+        if !is_player {
+            let carried = world
+                .get::<CarriedItems>(self.entity)
+                .map(|carried| carried.0.clone())
+                .unwrap_or_default();
+
+            if !carried.is_empty() {
+                a_drop(self.entity, carried).do_action(world);
+            }
+        }
+
         {
             let mut to_remove = vec![];
             if !is_player {
@@ -96,9 +113,11 @@ impl Action for DeathAction {
             }
         }
 
-        if let Some(sfx) = sfx {
-            play_sfx(sfx, world);
-        }
+        let killed = ActionOutcome::Killed {
+            who: self.entity,
+            player: is_player,
+        };
+        report(world, killed);
         result
     }
 }
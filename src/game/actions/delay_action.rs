@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::game::{character::CharacterStat, turns::TurnOrder};
+
+use super::{AbstractAction, Action, ActionKind, ActionResult};
+
+// puts an action off until `delay` more time has passed (see `turns::TURN`), whoever's turn it
+// happens to be by then
+#[derive(Debug)]
+pub struct DelayAction {
+    pub delay: u64,
+    pub action: ActionKind,
+}
+
+pub fn a_delay(delay: u64, action: ActionKind) -> AbstractAction {
+    Box::new(DelayAction { delay, action })
+}
+
+impl Action for DelayAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        self.action.clone().into_action().get_affiliated_stat()
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Delay {
+            delay: self.delay,
+            action: Box::new(self.action.clone()),
+        }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let action = self.action.clone().into_action();
+        world.resource_mut::<TurnOrder>().schedule(self.delay, action);
+        vec![]
+    }
+}
@@ -1,20 +1,22 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    character::{Character, CharacterStat}, grid::WorldEntity, health::{Health, HitPoint}, history::HistoryLog, inventory::Item, procgen::PlayerMarker
+    character::{Character, CharacterStat}, grid::WorldEntity, health::Health, history::{args, HistoryLog}, inventory::Item, procgen::PlayerMarker
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{sacrifice_action::AltarBlessing, AbstractAction, Action, ActionResult, ActionKind};
 use crate::game::actions::a_destroy;
 use crate::game::feel::Random;
 use crate::game::inventory::CarriedItems;
 use crate::game::inventory::EquippedItems;
 use crate::game::procgen::LevelDepth;
 #[derive(Debug)]
-pub struct DescendAction;
+pub struct DescendAction {
+    pub who: Entity,
+}
 
-pub fn a_descend() -> AbstractAction {
-    Box::new(DescendAction)
+pub fn a_descend(who: Entity) -> AbstractAction {
+    Box::new(DescendAction { who })
 }
 
 impl Action for DescendAction {
@@ -22,7 +24,18 @@ impl Action for DescendAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Descend { who: self.who }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
+        // an offering made at an altar stands in for the health
+        let mut blessed_query = world.query_filtered::<Entity, With<AltarBlessing>>();
+        let blessed = blessed_query.iter(world).collect::<Vec<_>>();
+        for entity in &blessed {
+            world.entity_mut(*entity).remove::<AltarBlessing>();
+        }
+
         let mut read_system_state = SystemState::<(
             Query<
                 (
@@ -45,8 +58,8 @@ impl Action for DescendAction {
         let (mut char, mut health, _, carried, mut equipped) = player_query.single_mut();
 
         let (stat, val) = char.get_strongest_stat();
-        if val < 9 {
-            log.add(&format!("You feel {} health wither away and go to the Healer.", 9 - val));
+        if val < 9 && blessed.is_empty() {
+            log.say("descend.wither", args().count(9 - val));
 
             let dval = (9 - val) as usize;
             if dval >= health.size {
@@ -1,9 +1,15 @@
 use bevy::prelude::*;
 
-use crate::game::character::CharacterStat;
+use crate::game::{
+    character::CharacterStat,
+    grid::WorldEntity,
+    history::{args, HistoryLog},
+    inventory::{CarriedMarker, Item},
+};
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionKind, ActionRejection, ActionResult};
 
+// turns something lying on the floor to dust, see `Spell::Disintegrate`
 #[derive(Debug)]
 pub struct DesintegrateAction {
     pub what: Entity,
@@ -18,7 +24,27 @@ impl Action for DesintegrateAction {
         CharacterStat::WIL
     }
 
-    fn do_action(&mut self, world: &mut World) -> ActionResult {
+    fn kind(&self) -> ActionKind {
+        ActionKind::Desintegrate { what: self.what }
+    }
+
+    // whatever was picked up in the meantime is safe
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        if world.get::<Item>(self.what).is_none() || world.get::<CarriedMarker>(self.what).is_some()
+        {
+            return Err(ActionRejection::Gone);
+        }
+
+        Ok(())
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        if let Some(item) = world.get::<WorldEntity>(self.what).cloned() {
+            world
+                .resource_mut::<HistoryLog>()
+                .say("spell.disintegrated", args().with("item", &item.name));
+        }
+
         let mut to_remove = vec![self.what];
         if let Some(ch) = world.get::<Children>(self.what) {
             for c in ch.iter() {
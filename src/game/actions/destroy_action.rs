@@ -6,7 +6,7 @@ use crate::game::{
     inventory::{CarriedItems, EquippedItems, Item},
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct DestroyAction {
@@ -22,6 +22,10 @@ impl Action for DestroyAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Destroy { what: self.what }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             ResMut<WorldData>,
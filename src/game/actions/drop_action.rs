@@ -3,13 +3,13 @@ use bevy::{ecs::system::SystemState, prelude::*};
 use crate::game::{
     character::{Character, CharacterStat},
     grid::{Grid, WorldEntity},
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::{CarriedItems, CarriedMarker, EquippedItems, Item},
     procgen::ClearLevel,
     turns::TurnTaker,
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct DropAction {
@@ -27,6 +27,13 @@ impl Action for DropAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Drop {
+            who: self.who,
+            what: self.what.clone(),
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             Query<(&mut WorldEntity, &mut Transform), Without<TurnTaker>>,
@@ -82,7 +89,10 @@ impl Action for DropAction {
                         person_char[*stat] -= *val;
                     }
 
-                    log.add(&format!("{} unequipped {}.", person_entity.name, item.name));
+                    log.say(
+                        "item.unequipped",
+                        args().subject(person_entity).with("item", &item.name),
+                    );
                     log_written = true;
                 }
                 *vis = Visibility::Visible;
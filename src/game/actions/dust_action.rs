@@ -0,0 +1,79 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use super::*;
+use crate::game::{
+    character::Character,
+    health::Health,
+    history::{args, HistoryLog},
+    magic::creatures_around,
+    outcomes::{report, ActionOutcome},
+};
+
+// how close to the player anyone has to be to have their health stolen
+const DUST_RANGE: i32 = 3;
+
+// takes a hitpoint from everyone around the player, and gives it to the player
+#[derive(Debug)]
+pub struct DustAction {
+    pub who: Entity,
+}
+
+pub fn a_dust(who: Entity) -> AbstractAction {
+    Box::new(DustAction { who })
+}
+
+impl Action for DustAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::WIL
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Dust { who: self.who }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let targets = creatures_around(world, self.who, DUST_RANGE);
+
+        let mut read_system_state = SystemState::<(
+            Query<(&mut Health, &mut Character)>,
+            ResMut<HistoryLog>,
+        )>::new(world);
+        let (mut creatures, mut log) = read_system_state.get_mut(world);
+
+        let mut result = vec![];
+        let mut damaged = vec![];
+        for target in targets {
+            let Ok((mut health, mut character)) = creatures.get_mut(target) else {
+                continue;
+            };
+
+            for (stat, val) in health.normal_damage(1) {
+                character[stat] += val;
+                *character.counters.entry(stat).or_insert(0) += 1;
+            }
+
+            damaged.push(target);
+            if health.hitpoints.is_empty() {
+                result.push(a_death(target));
+            }
+        }
+
+        if let Ok((mut health, _)) = creatures.get_mut(self.who) {
+            health.normal_heal(damaged.len());
+        }
+
+        log.say("aspect.dust", args().count(damaged.len() as i64));
+        log.add("");
+        report(world, ActionOutcome::SpellCast { who: self.who });
+        for target in damaged {
+            let stolen = ActionOutcome::Damaged {
+                attacker: self.who,
+                target,
+                amount: 1,
+                sneak: false,
+            };
+            report(world, stolen);
+        }
+        result
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use super::*;
+use crate::game::{
+    character::Character,
+    feel::Random,
+    health::Health,
+    history::{args, HistoryLog},
+    magic::{creatures_around, STATS},
+    outcomes::{report, ActionOutcome},
+    status::StatusEffect,
+};
+
+// how close to the player the edge cuts
+const EDGE_RANGE: i32 = 2;
+
+// curses everyone around the player with -1 of a random stat in their weakest hitpoint, which
+// bleeds until it's gone
+#[derive(Debug)]
+pub struct EdgeAction {
+    pub who: Entity,
+}
+
+pub fn a_edge(who: Entity) -> AbstractAction {
+    Box::new(EdgeAction { who })
+}
+
+impl Action for EdgeAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::AGI
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Edge { who: self.who }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let targets = creatures_around(world, self.who, EDGE_RANGE);
+
+        let mut read_system_state = SystemState::<(
+            Query<(&mut Health, &mut Character)>,
+            ResMut<Random>,
+            ResMut<HistoryLog>,
+        )>::new(world);
+        let (mut creatures, mut rng, mut log) = read_system_state.get_mut(world);
+
+        let mut count = 0;
+        for target in targets {
+            let Ok((mut health, mut character)) = creatures.get_mut(target) else {
+                continue;
+            };
+
+            let Some(hp) = health.hitpoints.back_mut() else {
+                continue;
+            };
+
+            for (stat, val) in hp.enchant((rng.from(&STATS), -1)) {
+                character[stat] += val;
+                *character.counters.entry(stat).or_insert(0) += 1;
+            }
+            hp.status = Some(StatusEffect::Bleeding);
+
+            count += 1;
+        }
+
+        log.say("aspect.edge", args().count(count));
+        log.add("");
+        report(world, ActionOutcome::SpellCast { who: self.who });
+        vec![]
+    }
+}
@@ -3,11 +3,15 @@ use bevy::{ecs::system::SystemState, prelude::*};
 use crate::game::{
     character::{Character, CharacterStat},
     grid::WorldEntity,
-    history::HistoryLog,
-    inventory::{CarriedItems, EquippedItems, Item, ItemType},
+    history::{args, HistoryLog},
+    inventory::{CarriedItems, EquippedItems, Item},
+    outcomes::{report, ActionOutcome},
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionKind, ActionRejection, ActionResult};
+
+// how many weapons and armor pieces anyone can have on at once
+pub const MAX_EQUIPPED: usize = 2;
 
 #[derive(Debug)]
 pub struct EquipAction {
@@ -24,6 +28,27 @@ impl Action for EquipAction {
         CharacterStat::AGI
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Equip {
+            who: self.who,
+            what: self.what,
+        }
+    }
+
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        let (Some(equipped), Some(_)) =
+            (world.get::<EquippedItems>(self.who), world.get::<Item>(self.what))
+        else {
+            return Err(ActionRejection::Gone);
+        };
+
+        if equipped.0.len() >= MAX_EQUIPPED && !equipped.0.contains(&self.what) {
+            return Err(ActionRejection::HandsFull);
+        }
+
+        Ok(())
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             ResMut<HistoryLog>,
@@ -43,33 +68,19 @@ impl Action for EquipAction {
         };
 
         let mut message = vec![];
+        let mut equipped_now = false;
         if let Ok((mut character, world_entity, carried, mut equipped)) =
             world_entity_query.get_mut(self.who)
         {
-            if equipped.0.len() >= 2 {
-                log.add("You try to wield three weapons, but it's simply too much... You're wasting time. Remember your training.");
-                return vec![];
-            }
-
-            if carried.0.iter().any(|i| *i == self.what)
-                && !equipped.0.iter().any(|i| *i == self.what)
+            if carried.0.contains(&self.what)
+                && !equipped.0.contains(&self.what)
             {
-                message.push(format!("{} equipped {}.", world_entity.name, item.name));
+                message.push(log.text(
+                    "item.equipped",
+                    args().subject(world_entity).with("item", &item.name),
+                ));
                 equipped.0.push(self.what);
-
-                let count_weapons = equipped
-                    .0
-                    .iter()
-                    .filter(|&i| {
-                        if let Ok(item) = item_query.get(*i) {
-                            item.item_type == ItemType::Weapon
-                        } else {
-                            false
-                        }
-                    })
-                    .count();
-
-                println!("Count weapons: {:?}", count_weapons);
+                equipped_now = true;
 
                 for (stat, val) in &item.equip_stat_changes {
                     character[*stat] += *val;
@@ -77,12 +88,12 @@ impl Action for EquipAction {
                         let e = character.counters.entry(*stat).or_insert(0);
                         *e += 1;
                     }
-                    message.push(format!(
-                        "{} {} {} by {}.",
-                        world_entity.name,
-                        if *val > 0 { "raise" } else { "lower" },
-                        format!("{:?}", *stat).to_uppercase(),
-                        val.abs()
+                    message.push(log.text(
+                        if *val > 0 { "stat.raise" } else { "stat.lower" },
+                        args()
+                            .subject(world_entity)
+                            .with("stat", format!("{:?}", *stat).to_uppercase())
+                            .with("amount", val.abs()),
                     ));
                 }
 
@@ -91,6 +102,13 @@ impl Action for EquipAction {
             }
         }
 
+        if equipped_now {
+            let equipped = ActionOutcome::ItemEquipped {
+                who: self.who,
+                what: self.what,
+            };
+            report(world, equipped);
+        }
         vec![]
     }
 }
@@ -1,10 +1,14 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    actions::a_move, character::CharacterStat, grid::WorldEntity, procgen::PlayerMarker,
+    actions::a_move,
+    character::CharacterStat,
+    grid::WorldEntity,
+    navigation::{NavigationGoal, NavigationParams},
+    procgen::PlayerMarker,
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct FleeAction {
@@ -21,10 +25,20 @@ impl Action for FleeAction {
         CharacterStat::AGI
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Flee {
+            who: self.who,
+            target: self.target,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let mut world_state =
-            SystemState::<(Query<&WorldEntity>, Query<Entity, With<PlayerMarker>>)>::new(world);
-        let (world_state_query, player_entities) = world_state.get_mut(world);
+        let mut world_state = SystemState::<(
+            Query<&WorldEntity>,
+            Query<Entity, With<PlayerMarker>>,
+            NavigationParams,
+        )>::new(world);
+        let (world_state_query, player_entities, mut navigation) = world_state.get_mut(world);
 
         let Ok(player_entity) = player_entities.get_single() else {
             return vec![];
@@ -46,6 +60,12 @@ impl Action for FleeAction {
             return vec![];
         };
 
+        if let Some(step) =
+            navigation.downhill(NavigationGoal::Flee, *npc_position, *player_position)
+        {
+            return vec![a_move(self.who, step - *npc_position)];
+        }
+
         let dp = *npc_position - *player_position;
         let norm_dp = dp.clamp(IVec2::new(-1, -1), IVec2::new(1, 1));
 
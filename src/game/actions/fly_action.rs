@@ -4,10 +4,10 @@ use crate::game::{
     actions::a_break,
     character::CharacterStat,
     grid::{Grid, WorldData, WorldEntity},
-    history::HistoryLog,
+    history::{args, HistoryLog},
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct FlyAction {
@@ -29,6 +29,14 @@ impl Action for FlyAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Fly {
+            what: self.what,
+            path: self.path.clone(),
+            already_flying: self.already_flying,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             Query<(&mut WorldEntity, &mut Transform)>,
@@ -66,7 +74,7 @@ impl Action for FlyAction {
             // log.add(&format!("FLYING AT {:?}", &item_world.position,));
             vec![a_fly(self.what, self.path[1..].to_vec(), true)]
         } else {
-            log.add("The thrown item falls down.");
+            log.say("throw.falls", args());
             log.add("");
             vec![a_break(self.what)]
         }
@@ -1,10 +1,10 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    actions::play_sfx, character::{Character, CharacterStat}, grid::WorldEntity, health::Health, history::HistoryLog
+    character::{Character, CharacterStat}, grid::WorldEntity, health::Health, history::{args, HistoryLog}, outcomes::{report, ActionOutcome}
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 use crate::game::magic::Focus;
 
 #[derive(Debug)]
@@ -21,6 +21,10 @@ impl Action for FocusAction {
         CharacterStat::ARC
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Focus { who: self.who }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             ResMut<HistoryLog>,
@@ -29,7 +33,7 @@ impl Action for FocusAction {
 
         let (mut log, mut world_entity_query) = read_system_state.get_mut(world);
 
-        let Ok((char, entity, mut focus, health)) = world_entity_query.get_mut(self.who) else {
+        let Ok((_char, entity, mut focus, health)) = world_entity_query.get_mut(self.who) else {
             return vec![];
         };
 
@@ -38,25 +42,18 @@ impl Action for FocusAction {
             focus.0 = 0;
         }
 
-        let mut log_written = false;
         if entity.is_player {
             if focus.0 > 0 {
-                log.add(&format!("Your focus is raised to {}.", focus.0));
-                log_written = true;
+                log.say("focus.raised", args().count(focus.0));
             } else {
-                log.add("You focus. You can implant consumed thaumaturgy deeper into your soul.");
-                log_written = true;
+                log.say("focus.reset", args());
             }
         } else {
-            log.add(&format!("{} focuses.", entity.name));
-            log_written = true;
+            log.say("focus", args().subject(entity));
         }
         log.add("");
-        
-        if entity.is_player {
-            play_sfx("gameplay_surprise", world);
-        }
-        
+
+        report(world, ActionOutcome::Focused { who: self.who });
         vec![]
     }
 }
@@ -1,17 +1,18 @@
 
 use bevy::{ecs::system::SystemState, prelude::*};
 
-use crate::game::{actions::a_destroy, character::CharacterStat, health::Health, history::HistoryLog, player::Achievements, procgen::PlayerMarker};
+use crate::game::{actions::a_destroy, character::CharacterStat, health::Health, history::{args, HistoryLog}, player::Achievements, procgen::PlayerMarker};
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct FortuneAction {
+    pub who: Entity,
     pub what: Entity,
 }
 
-pub fn a_fortune(what: Entity) -> AbstractAction {
-    Box::new(FortuneAction { what })
+pub fn a_fortune(who: Entity, what: Entity) -> AbstractAction {
+    Box::new(FortuneAction { who, what })
 }
 
 impl Action for FortuneAction {
@@ -19,19 +20,28 @@ impl Action for FortuneAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Fortune {
+            who: self.who,
+            what: self.what,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let message = {
-            if let Some(mut ach) = world.get_resource_mut::<Achievements>() {
-                ach.messages.pop().unwrap_or("...nothing at all!".to_string())
-            } else {
-                "...something sadly illegible...".to_string()
-            }
-        };
+        let message = world
+            .get_resource_mut::<Achievements>()
+            .map(|mut ach| ach.messages.pop());
         
         if let Some(mut log) = world.get_resource_mut::<HistoryLog>() {
-            log.add("You examine the scroll you found. It says:");
+            let message = match message {
+                Some(Some(message)) => message,
+                Some(None) => log.text("writ.empty", args()),
+                None => log.text("writ.illegible", args()),
+            };
+
+            log.say("writ.read", args());
             log.add(&message);
-            log.add("You feel your psyche heal a bit in contact with the outside world.");
+            log.say("writ.psyche", args());
         }
 
         let mut read_system_state = SystemState::<
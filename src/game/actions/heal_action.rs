@@ -2,10 +2,9 @@ use bevy::{ecs::system::SystemState, prelude::*};
 
 use super::*;
 use crate::game::{
-    character::Character, feel::Random, grid::WorldEntity, health::Health, history::HistoryLog,
-    inventory::EquippedItems, procgen::PlayerMarker,
+    character::Character, grid::WorldEntity, health::Health, history::{args, HistoryLog},
+    outcomes::{report, ActionOutcome},
 };
-use bevy_trauma_shake::Shake;
 
 #[derive(Debug)]
 pub struct HealAction {
@@ -21,6 +20,12 @@ impl Action for HealAction {
         CharacterStat::WIS
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Heal {
+            entity: self.entity,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             Query<(&mut Health, &mut Character, &WorldEntity)>,
@@ -30,7 +35,7 @@ impl Action for HealAction {
         let (mut world_health_query, mut log) =
             read_system_state.get_mut(world);
 
-        let Ok((mut target_health, mut target_character, world_target)) =
+        let Ok((mut target_health, _target_character, world_target)) =
             world_health_query.get_mut(self.entity)
         else {
             return vec![];
@@ -38,13 +43,10 @@ impl Action for HealAction {
 
         target_health.normal_heal(1);
 
-        log.add(&format!(
-            "{} heals up.",
-            world_target.name,
-        ));
+        log.say("heal", args().subject(world_target));
         log.add("");
-        
-        play_sfx("gameplay_surprise", world);
+
+        report(world, ActionOutcome::Healed { who: self.entity });
         vec![]
     }
 }
@@ -2,10 +2,9 @@ use bevy::{ecs::system::SystemState, prelude::*};
 
 use super::*;
 use crate::game::{
-    character::Character, feel::Random, grid::WorldEntity, health::Health, history::HistoryLog,
-    inventory::EquippedItems, procgen::PlayerMarker,
+    ai::awareness::Awareness, character::Character, feel::Random, grid::WorldEntity, health::Health, history::{args, HistoryLog},
+    inventory::EquippedItems, noise::{make_noise, Noise}, outcomes::{report, ActionOutcome}, procgen::PlayerMarker,
 };
-use bevy_trauma_shake::Shake;
 
 #[derive(Debug)]
 pub struct HitAction {
@@ -22,17 +21,30 @@ impl Action for HitAction {
         CharacterStat::STR
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Hit {
+            attacker: self.attacker,
+            target: self.target,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             Query<(&mut Health, &mut Character, Option<&mut EquippedItems>)>,
             Query<&PlayerMarker>,
             Query<&WorldEntity>,
-            Query<&mut Shake>,
+            Query<&mut Awareness>,
             ResMut<Random>,
             ResMut<HistoryLog>,
         )>::new(world);
-        let (mut world_health_query, player_query, world_query, mut shake_query, mut rng, mut log) =
-            read_system_state.get_mut(world);
+        let (
+            mut world_health_query,
+            player_query,
+            world_query,
+            mut awareness_query,
+            mut rng,
+            mut log,
+        ) = read_system_state.get_mut(world);
 
         let attacker_strength = {
             if let Ok((_, attacker_character, _attacker_equipped)) =
@@ -62,31 +74,42 @@ impl Action for HitAction {
             damage_amount = 1;
         }
 
-        if rng.percent(
+        // a monster that hasn't noticed the player can't get out of the way, and takes it hard.
+        // either way, it knows where the player is now.
+        let mut sneak_attack = false;
+        if player_query.contains(self.attacker) {
+            if let Ok(mut awareness) = awareness_query.get_mut(self.target) {
+                sneak_attack = !awareness.has_noticed();
+                *awareness = Awareness::Hunting {
+                    last_seen: world_attacker.position,
+                };
+            }
+        }
+
+        if sneak_attack {
+            damage_amount *= 2;
+            log.say("hit.sneak", args().with("target", world_target.name.to_lowercase()));
+        } else if rng.percent(
             100 - (target_character.agility * target_character.willpower).clamp(0, 50) as usize,
         ) {
-            if world_target.name == "You" {
-                log.add(&format!("{} move out of the way.", world_target.name));
-            } else {
-                log.add(&format!("{} moves out of the way.", world_target.name));
-            }
+            log.say("hit.dodge", args().subject(world_target));
 
-            play_sfx("gameplay_surprise", world);
+            let dodged = ActionOutcome::Dodged {
+                attacker: self.attacker,
+                target: self.target,
+            };
+            report(world, dodged);
+            make_noise(world, self.attacker, Noise::Hit);
             return vec![];
         }
 
-        let verb = if world_attacker.name == "You" {
-            "do"
-        } else {
-            "does"
-        };
-        log.add(&format!(
-            "{} {} {} damage to {}.",
-            world_attacker.name,
-            verb,
-            damage_amount,
-            world_target.name.to_lowercase()
-        ));
+        log.say(
+            "hit.damage",
+            args()
+                .subject(world_attacker)
+                .count(damage_amount)
+                .with("target", world_target.name.to_lowercase()),
+        );
         log.add("");
         let diff = target_health.normal_damage(damage_amount as usize);
         for (stat, val) in diff {
@@ -97,17 +120,20 @@ impl Action for HitAction {
             }
         }
 
-        if player_query.contains(self.target) {
-            shake_query.single_mut().add_trauma(rng.gen(2..5) as f32 * 0.01);
-        }
-
         let result = if target_health.hitpoints.is_empty() {
             vec![a_death(self.target)]
         } else {
             vec![]
         };
 
-        play_sfx("gameplay_hit", world);
+        let damaged = ActionOutcome::Damaged {
+            attacker: self.attacker,
+            target: self.target,
+            amount: damage_amount as usize,
+            sneak: sneak_attack,
+        };
+        report(world, damaged);
+        make_noise(world, self.attacker, Noise::Hit);
         result
     }
 }
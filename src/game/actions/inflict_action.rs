@@ -1,17 +1,19 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    actions::{a_random_walk, play_sfx},
     character::{Character, CharacterStat},
     feel::Random,
     grid::{Grid, WorldData, WorldEntity},
     health::Health,
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::Item,
     magic::Focus,
+    noise::{make_noise, Noise},
+    outcomes::{report, ActionOutcome},
+    status::{is_afflicted, StatusEffect},
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct InflictAction {
@@ -33,7 +35,22 @@ impl Action for InflictAction {
         CharacterStat::STR
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Inflict {
+            who: self.who,
+            target: self.target,
+            artifact: self.artifact.clone(),
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
+        if is_afflicted(world, self.who, StatusEffect::Silenced) {
+            if let Some(who) = world.get::<WorldEntity>(self.who).cloned() {
+                world.resource_mut::<HistoryLog>().say("effect.mute", args().subject(&who));
+            }
+            return vec![];
+        }
+
         let mut read_system_state = SystemState::<(
             Query<(&mut WorldEntity, &mut Character, &mut Health)>,
             Query<Option<&Focus>>,
@@ -44,16 +61,16 @@ impl Action for InflictAction {
             ResMut<Random>,
         )>::new(world);
 
-        let (mut entities, focus, _items, mut log, grid, world_data, mut rng) =
+        let (mut entities, focus, _items, mut log, grid, _world_data, mut rng) =
             read_system_state.get_mut(world);
 
-        let ((x, y), name) = {
+        let ((_x, _y), chanter) = {
             if let Ok((attacker_entity, _attacker_char, _attacker_health)) =
                 entities.get_mut(self.who)
             {
                 (
                     grid.norm(attacker_entity.position),
-                    attacker_entity.name.clone(),
+                    args().subject(&attacker_entity),
                 )
             } else {
                 return vec![];
@@ -66,10 +83,11 @@ impl Action for InflictAction {
             return vec![];
         };
 
-        log.add(&format!("{} chants in tongues.", name));
+        log.say("inflict.chant", chanter);
 
         if rng.percent(100 - (15i32 + target_char.arcana * 2).clamp(1, 30) as u32) {
-            log.add("You momentarily felt a spell affect you, but then it dissipates.");
+            log.say("inflict.dissipates", args());
+            report(world, ActionOutcome::SpellResisted { who: self.target });
             return vec![];
         }
 
@@ -79,22 +97,25 @@ impl Action for InflictAction {
             0
         });
 
-        let hp_total = (target_health.hitpoints.len() as isize - 1);
-        let mut already_missed = false;
+        let hp_total = target_health.hitpoints.len() as isize - 1;
+        let already_missed = false;
 
         let mut count = 0;
+        let mut enchanted = vec![];
 
         for (index, effect_val) in self.artifact.equip_stat_changes.iter().enumerate() {
             let pos = hp_total - index as isize - focus.0 as isize;
 
             if pos < 0 && !already_missed {
-                log.add("Your aura blocks part of an incoming spell.");
+                log.say("inflict.aura", args());
+                report(world, ActionOutcome::SpellResisted { who: self.target });
                 return vec![];
             }
 
             if let Some(hp) = target_health.hitpoints.get_mut(pos as usize) {
                 for (effect, val) in hp.enchant(*effect_val) {
                     target_char[effect] += val;
+                    enchanted.push((effect, val));
 
                     {
                         let e = target_char.counters.entry(effect).or_insert(0);
@@ -106,12 +127,18 @@ impl Action for InflictAction {
             count += 1;
         }
 
-        log.add(&format!(
-            "You are afflicted by a curse with {} effects!",
-            count
-        ));
+        log.say("inflict.cursed", args().count(count));
         log.add("");
-        play_sfx("item_cast", world);
+        report(world, ActionOutcome::SpellCast { who: self.who });
+        for (stat, amount) in enchanted {
+            let enchanted = ActionOutcome::HitPointEnchanted {
+                who: self.target,
+                stat,
+                amount,
+            };
+            report(world, enchanted);
+        }
+        make_noise(world, self.who, Noise::Chant);
         vec![]
     }
 }
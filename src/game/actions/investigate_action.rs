@@ -0,0 +1,46 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use crate::game::{
+    actions::a_move, character::CharacterStat, grid::WorldEntity, navigation::NavigationParams,
+};
+
+use super::{AbstractAction, Action, ActionKind, ActionResult};
+
+// walks towards a place rather than someone: where the player was last seen, say
+#[derive(Debug)]
+pub struct InvestigateAction {
+    pub who: Entity,
+    pub at: IVec2,
+}
+
+pub fn a_investigate(who: Entity, at: IVec2) -> AbstractAction {
+    Box::new(InvestigateAction { who, at })
+}
+
+impl Action for InvestigateAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::WIS
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Investigate {
+            who: self.who,
+            at: self.at,
+        }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let mut world_state = SystemState::<(Query<&WorldEntity>, NavigationParams)>::new(world);
+        let (world_state_query, mut navigation) = world_state.get_mut(world);
+
+        let Ok(WorldEntity { position, .. }) = world_state_query.get(self.who) else {
+            return vec![];
+        };
+
+        let Some(step) = navigation.next_step(*position, self.at) else {
+            return vec![];
+        };
+
+        vec![a_move(self.who, step - *position)]
+    }
+}
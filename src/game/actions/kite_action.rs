@@ -0,0 +1,63 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use crate::game::{
+    actions::a_move,
+    character::CharacterStat,
+    grid::WorldEntity,
+    navigation::{NavigationGoal, NavigationParams},
+};
+
+use super::{AbstractAction, Action, ActionKind, ActionResult};
+
+// keeps `who` at casting range from `target`: closer if too far, away if too close
+#[derive(Debug)]
+pub struct KiteAction {
+    pub who: Entity,
+    pub target: Entity,
+}
+
+pub fn a_kite(who: Entity, target: Entity) -> AbstractAction {
+    Box::new(KiteAction { who, target })
+}
+
+impl Action for KiteAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::AGI
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Kite {
+            who: self.who,
+            target: self.target,
+        }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let mut world_state = SystemState::<(Query<&WorldEntity>, NavigationParams)>::new(world);
+        let (world_state_query, mut navigation) = world_state.get_mut(world);
+
+        let Ok(WorldEntity {
+            position: npc_position,
+            ..
+        }) = world_state_query.get(self.who)
+        else {
+            return vec![];
+        };
+
+        let Ok(WorldEntity {
+            position: target_position,
+            ..
+        }) = world_state_query.get(self.target)
+        else {
+            return vec![];
+        };
+
+        // already at a good distance: stay put
+        let Some(step) = navigation.downhill(NavigationGoal::Kite, *npc_position, *target_position)
+        else {
+            return vec![];
+        };
+
+        vec![a_move(self.who, step - *npc_position)]
+    }
+}
@@ -4,11 +4,10 @@ use crate::game::{
     character::CharacterStat,
     fov::RecalculateFOVEvent,
     grid::Grid,
-    inventory::{ItemBuilder, ItemType},
-    sprites::BONES,
+    inventory::item_from_template,
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct LeaveBonesAction {
@@ -25,12 +24,18 @@ impl Action for LeaveBonesAction {
         CharacterStat::WIS
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::LeaveBones {
+            stats: self.stats.clone(),
+            pos: self.pos,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let item = ItemBuilder::default()
-            .with_name("Bones")
-            .with_image(BONES)
-            .with_type(ItemType::Artifact)
-            .with_stats(&self.stats);
+        let Some(item) = item_from_template(world, "bones") else {
+            return vec![];
+        };
+        let item = item.with_stats(&self.stats);
 
         //let (transform, atlas) = {
         let mut read_system_state =
@@ -0,0 +1,79 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use super::*;
+use crate::game::{
+    feel::Random,
+    grid::{Grid, WorldData, WorldEntity},
+    history::{args, HistoryLog},
+    navigation::is_walkable,
+    outcomes::{report, ActionOutcome},
+};
+
+// how many acolytes that came before leave their bones around the player
+const LORE_BONES: usize = 2;
+
+// calls up the bones of acolytes past, steeped in the stats that woke the aspect
+#[derive(Debug)]
+pub struct LoreAction {
+    pub who: Entity,
+    pub stats: Vec<CharacterStat>,
+}
+
+pub fn a_lore(who: Entity, stats: Vec<CharacterStat>) -> AbstractAction {
+    Box::new(LoreAction { who, stats })
+}
+
+impl Action for LoreAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::WIS
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Lore {
+            who: self.who,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let mut read_system_state = SystemState::<(
+            Query<&WorldEntity>,
+            Res<Grid>,
+            Res<WorldData>,
+            ResMut<Random>,
+            ResMut<HistoryLog>,
+        )>::new(world);
+        let (world_entities, grid, world_data, mut rng, mut log) =
+            read_system_state.get_mut(world);
+
+        let Ok(world_entity) = world_entities.get(self.who) else {
+            return vec![];
+        };
+
+        let mut free = vec![];
+        for x in -1..=1 {
+            for y in -1..=1 {
+                let at = world_entity.position + IVec2::new(x, y);
+                if at != world_entity.position
+                    && is_walkable(&grid, &world_data, at)
+                    && !world_data.blocking.contains_key(&at)
+                {
+                    free.push(at);
+                }
+            }
+        }
+
+        let stats = self.stats.iter().map(|stat| (*stat, 1)).collect::<Vec<_>>();
+        let bones = rng
+            .shuffle(free)
+            .into_iter()
+            .take(LORE_BONES)
+            .map(|at| a_leave_bones(stats.clone(), at))
+            .collect::<Vec<_>>();
+
+        log.say("aspect.lore", args().count(bones.len() as i64));
+        log.add("");
+        report(world, ActionOutcome::SpellCast { who: self.who });
+        bones
+    }
+}
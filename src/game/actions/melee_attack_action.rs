@@ -1,7 +1,10 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use super::*;
-use crate::game::grid::{WorldData, WorldEntity};
+use crate::game::{
+    ai::factions::Faction,
+    grid::{WorldData, WorldEntity},
+};
 
 #[derive(Debug)]
 pub struct MeleeAttackAction {
@@ -21,17 +24,27 @@ impl Action for MeleeAttackAction {
         CharacterStat::STR
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Melee {
+            entity: self.entity,
+            direction: self.direction,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state =
-            SystemState::<(Res<WorldData>, Query<&WorldEntity>)>::new(world);
+            SystemState::<(Res<WorldData>, Query<(&WorldEntity, Option<&Faction>)>)>::new(world);
 
         let (world_data, world_entities) = read_system_state.get(world);
 
-        let Ok(WorldEntity {
-            is_player: is_attacker_player,
-            position,
-            ..
-        }) = world_entities.get(self.entity)
+        let Ok((
+            WorldEntity {
+                is_player: is_attacker_player,
+                position,
+                ..
+            },
+            attacker_faction,
+        )) = world_entities.get(self.entity)
         else {
             return vec![];
         };
@@ -39,15 +52,20 @@ impl Action for MeleeAttackAction {
         let next_position = *position + self.direction;
 
         if let Some(other) = world_data.blocking.get(&next_position) {
-            let Ok(WorldEntity {
-                is_player: is_target_player,
-                ..
-            }) = world_entities.get(*other)
+            let Ok((
+                WorldEntity {
+                    is_player: is_target_player,
+                    ..
+                },
+                target_faction,
+            )) = world_entities.get(*other)
             else {
                 return vec![];
             };
 
-            if is_attacker_player != is_target_player {
+            if is_attacker_player != is_target_player
+                || Faction::hostile(attacker_faction, target_faction)
+            {
                 vec![a_hit(self.entity, *other)]
             } else {
                 vec![]
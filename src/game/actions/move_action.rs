@@ -3,7 +3,11 @@ use bevy::{ecs::system::SystemState, prelude::*};
 use crate::game::{
     fov::RecalculateFOVEvent,
     grid::{Grid, WorldData, WorldEntity},
+    history::{args, HistoryLog},
+    noise::{make_noise, Noise},
+    outcomes::{report, ActionOutcome},
     procgen::PlayerMarker,
+    status::{is_afflicted, StatusEffect},
 };
 
 use super::*;
@@ -35,6 +39,26 @@ impl Action for MoveAction {
         CharacterStat::AGI
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Move {
+            entity: self.entity,
+            direction: self.direction,
+        }
+    }
+
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        let Some(world_entity) = world.get::<WorldEntity>(self.entity) else {
+            return Err(ActionRejection::Gone);
+        };
+
+        let world_data = world.resource::<WorldData>();
+        if world_data.solid.contains(&(world_entity.position + self.direction)) {
+            return Err(ActionRejection::Blocked);
+        }
+
+        Ok(())
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         if self.direction == IVec2::ZERO {
             return vec![];
@@ -73,6 +97,16 @@ impl Action for MoveAction {
             }
         };
 
+        // the rooted can still lash out at whoever's next to them, but can't go anywhere
+        if matches!(move_result, MoveResult::MoveSucceed { .. })
+            && is_afflicted(world, self.entity, StatusEffect::Rooted)
+        {
+            if let Some(who) = world.get::<WorldEntity>(self.entity).cloned() {
+                world.resource_mut::<HistoryLog>().say("effect.stuck", args().subject(&who));
+            }
+            return vec![];
+        }
+
         // by the end of this, we have free'd the world, so we can now do mut stuff
         match move_result {
             MoveResult::MoveSucceed {
@@ -106,7 +140,13 @@ impl Action for MoveAction {
                     fov_events.send(RecalculateFOVEvent);
                 }
 
-                play_sfx("gameplay_step", world);
+                let moved = ActionOutcome::Moved {
+                    who: self.entity,
+                    from: next_position - self.direction,
+                    to: next_position,
+                };
+                report(world, moved);
+                make_noise(world, self.entity, Noise::Step);
                 vec![]
             }
 
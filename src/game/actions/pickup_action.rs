@@ -1,10 +1,10 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    actions::play_sfx, character::CharacterStat, grid::WorldEntity, history::HistoryLog, inventory::{CarriedItems, CarriedMarker, Item}, procgen::ClearLevel
+    character::CharacterStat, grid::WorldEntity, history::{args, HistoryLog}, inventory::{CarriedItems, CarriedMarker, Item}, outcomes::{report, ActionOutcome}, procgen::ClearLevel
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionKind, ActionRejection, ActionResult};
 
 #[derive(Debug)]
 pub struct PickupAction {
@@ -21,8 +21,26 @@ impl Action for PickupAction {
         CharacterStat::AGI
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Pickup {
+            who: self.who,
+            what: self.what.clone(),
+        }
+    }
+
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        let Some(carried) = world.get::<CarriedItems>(self.who) else {
+            return Err(ActionRejection::Gone);
+        };
+
+        if carried.0.len() >= 9 {
+            return Err(ActionRejection::NoSpace);
+        }
+
+        Ok(())
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let mut pickup_success = false;
         let mut clear_items = vec![];
 
         let mut read_system_state = SystemState::<(
@@ -52,15 +70,14 @@ impl Action for PickupAction {
                 mark_carried.push(*item_entity);
 
                 if person_entity.is_player {
-                    pickup_success = true;
-                    log.add(&format!("Picked up {}.", item.name));
+                    log.say("item.picked_up", args().with("item", &item.name));
                     log.add("");
                     if clear.contains(*item_entity) {
                         clear_items.push(*item_entity);
                     }
                 }
             } else {
-                log.add("No more space for items. Drop something first.");
+                log.say("item.no_space", args());
                 log.add("");
             }
         }
@@ -68,6 +85,11 @@ impl Action for PickupAction {
         {
             for marked in mark_carried {
                 world.entity_mut(marked).insert(CarriedMarker);
+                let picked_up = ActionOutcome::ItemPickedUp {
+                    who: self.who,
+                    what: marked,
+                };
+                report(world, picked_up);
             }
         }
 
@@ -77,10 +99,6 @@ impl Action for PickupAction {
             }
         }
 
-        if pickup_success {
-            play_sfx("item_pickup", world);
-        }
-
         vec![]
     }
 }
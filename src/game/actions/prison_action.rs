@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use super::*;
+use crate::game::{
+    history::{args, HistoryLog},
+    magic::creatures_around,
+    outcomes::{report, ActionOutcome},
+    status::{inflict, StatusEffect},
+};
+
+// how far from the player the prison reaches, and for how many turns it burns
+const PRISON_RANGE: i32 = 4;
+const PRISON_BURN_TURNS: u32 = 3;
+
+// sets the ground around every enemy near the player on fire, burning them and anyone next
+// to them (apart from the player)
+#[derive(Debug)]
+pub struct PrisonAction {
+    pub who: Entity,
+}
+
+pub fn a_prison(who: Entity) -> AbstractAction {
+    Box::new(PrisonAction { who })
+}
+
+impl Action for PrisonAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::ARC
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Prison { who: self.who }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let enemies = creatures_around(world, self.who, PRISON_RANGE);
+
+        let mut burning = vec![];
+        for enemy in enemies {
+            for other in std::iter::once(enemy).chain(creatures_around(world, enemy, 1)) {
+                if other != self.who && !burning.contains(&other) {
+                    burning.push(other);
+                }
+            }
+        }
+
+        for target in burning {
+            inflict(world, target, StatusEffect::Burning, PRISON_BURN_TURNS);
+        }
+
+        if let Some(mut log) = world.get_resource_mut::<HistoryLog>() {
+            log.say("aspect.prison", args());
+            log.add("");
+        }
+
+        report(world, ActionOutcome::SpellCast { who: self.who });
+        vec![]
+    }
+}
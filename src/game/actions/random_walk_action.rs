@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::game::{actions::a_move, character::CharacterStat, feel::Random};
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct RandomWalkAction {
@@ -18,6 +18,10 @@ impl Action for RandomWalkAction {
         CharacterStat::AGI
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::RandomWalk { who: self.who }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let Some(mut rng) = world.get_resource_mut::<Random>() else {
             return vec![];
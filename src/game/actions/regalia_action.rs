@@ -0,0 +1,84 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use super::*;
+use crate::game::{
+    feel::Random,
+    fov::RecalculateFOVEvent,
+    grid::{Grid, WorldData},
+    history::{args, HistoryLog},
+    inventory::item_from_template,
+    magic::Magic,
+    navigation::is_walkable,
+    outcomes::{report, ActionOutcome},
+};
+
+// how many artifacts get scattered around the level
+const REGALIA_COUNT: usize = 3;
+
+// scatters artifacts around the level, each one raising the two stats of an aspect
+#[derive(Debug)]
+pub struct RegaliaAction {
+    pub who: Entity,
+}
+
+pub fn a_regalia(who: Entity) -> AbstractAction {
+    Box::new(RegaliaAction { who })
+}
+
+impl Action for RegaliaAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::INT
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Regalia { who: self.who }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let placed = {
+            let mut read_system_state =
+                SystemState::<(Res<Grid>, Res<WorldData>, Res<Magic>, ResMut<Random>)>::new(world);
+            let (grid, world_data, magic, mut rng) = read_system_state.get_mut(world);
+
+            let mut free = grid
+                .entities
+                .keys()
+                .filter(|at| {
+                    is_walkable(&grid, &world_data, **at) && !world_data.blocking.contains_key(*at)
+                })
+                .copied()
+                .collect::<Vec<_>>();
+            free.sort_by_key(|at| (at.x, at.y));
+
+            let bindings = magic.bindings();
+            if bindings.is_empty() {
+                return vec![];
+            }
+
+            rng.shuffle(free)
+                .into_iter()
+                .take(REGALIA_COUNT)
+                .map(|at| (at, rng.from(&bindings).1))
+                .collect::<Vec<_>>()
+        };
+
+        for (at, [a, b]) in placed {
+            let Some(item) = item_from_template(world, "regalia") else {
+                return vec![];
+            };
+            let item = item.with_stats(&[(a, 1), (b, 1)]);
+
+            let grid = world.resource::<Grid>();
+            let mut transform = grid.get_tile_position(at);
+            transform.translation.z = 1.0;
+            let atlas = grid.atlas.clone_weak();
+            item.create_at_raw(at, world, transform, atlas);
+        }
+
+        world.send_event(RecalculateFOVEvent);
+        world.resource_mut::<HistoryLog>().say("aspect.regalia", args());
+        world.resource_mut::<HistoryLog>().add("");
+        report(world, ActionOutcome::SpellCast { who: self.who });
+        vec![]
+    }
+}
@@ -1,14 +1,22 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
+    ai::items::worth,
     character::{Character, CharacterStat},
     grid::WorldEntity,
-    history::HistoryLog,
+    history::{args, HistoryLog},
+    inventory::{CarriedItems, EquippedItems, Item},
+    player::PlayerState,
+    procgen::{Altar, LevelDepth, ProcGenEvent},
 };
 
-use super::{AbstractAction, Action, ActionResult};
-use crate::game::magic::Focus;
+use super::{a_descend, a_destroy, AbstractAction, Action, ActionKind, ActionRejection, ActionResult};
 
+// left on whoever made an offering at an altar, so that going down doesn't cost them health
+#[derive(Component)]
+pub struct AltarBlessing;
+
+// what `M` does at an altar: the best of what's carried goes instead of health
 #[derive(Debug)]
 pub struct SacrificeAction {
     pub who: Entity,
@@ -23,13 +31,76 @@ impl Action for SacrificeAction {
         CharacterStat::ARC
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Sacrifice { who: self.who }
+    }
+
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        let Some(world_entity) = world.get::<WorldEntity>(self.who) else {
+            return Err(ActionRejection::Gone);
+        };
+
+        let position = world_entity.position;
+        let at_altar = world
+            .iter_entities()
+            .filter(|entity| entity.contains::<Altar>())
+            .filter_map(|entity| entity.get::<WorldEntity>())
+            .any(|altar| altar.position == position);
+        if !at_altar {
+            return Err(ActionRejection::NoAltar);
+        }
+
+        if world.get::<CarriedItems>(self.who).is_none_or(|carried| carried.0.is_empty()) {
+            return Err(ActionRejection::NoOffering);
+        }
+
+        Ok(())
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let mut read_system_state =
-            SystemState::<(ResMut<HistoryLog>, Query<(&mut Character, &WorldEntity)>)>::new(world);
+        let mut read_system_state = SystemState::<(
+            Query<(&mut Character, &mut CarriedItems, &mut EquippedItems)>,
+            Query<&Item>,
+            ResMut<HistoryLog>,
+            ResMut<LevelDepth>,
+            ResMut<PlayerState>,
+            EventWriter<ProcGenEvent>,
+        )>::new(world);
+
+        let (mut carriers, items, mut log, mut depth, mut player_state, mut procgen_events) =
+            read_system_state.get_mut(world);
+
+        let Ok((mut character, mut carried, mut equipped)) = carriers.get_mut(self.who) else {
+            return vec![];
+        };
+
+        let Some((offering, item)) = carried
+            .0
+            .iter()
+            .filter_map(|entity| items.get(*entity).ok().map(|item| (*entity, item)))
+            .max_by_key(|(_, item)| worth(item))
+        else {
+            return vec![];
+        };
+
+        if let Some(pos) = equipped.0.iter().position(|e| *e == offering) {
+            equipped.0.remove(pos);
+            for (stat, val) in &item.equip_stat_changes {
+                character[*stat] -= *val;
+            }
+        }
+        carried.0.retain(|e| *e != offering);
+
+        log.say("altar.sacrifice", args().with("item", &item.name));
+        log.say("descend", args());
+        log.say("descend.separator", args());
 
-        let (mut log, mut world_entity_query) = read_system_state.get_mut(world);
+        // the same way down as `M` takes, see `character_controls`
+        procgen_events.send(ProcGenEvent::NextLevel);
+        depth.0 += 1;
+        *player_state = PlayerState::Descended;
 
-        log.add("You make the sacrifice to descend into the ruin.");
-        vec![]
+        world.entity_mut(self.who).insert(AltarBlessing);
+        vec![a_destroy(offering), a_descend(self.who)]
     }
 }
@@ -0,0 +1,77 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use crate::game::{
+    actions::{a_move, a_pickup, a_random_walk},
+    ai::items::worth_taking,
+    character::CharacterStat,
+    grid::WorldEntity,
+    inventory::{CarriedMarker, Item},
+    navigation::{NavigationGoal, NavigationParams},
+    procgen::PlayerMarker,
+};
+
+use super::{AbstractAction, Action, ActionKind, ActionResult};
+
+// how many steps away a monster notices something worth taking
+const SCAVENGE_RANGE: i32 = 8;
+
+// picks up whatever's worth taking underfoot, or heads for the nearest such thing around.
+// with nothing around, just wanders.
+#[derive(Debug)]
+pub struct ScavengeAction {
+    pub who: Entity,
+}
+
+pub fn a_scavenge(who: Entity) -> AbstractAction {
+    Box::new(ScavengeAction { who })
+}
+
+impl Action for ScavengeAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::AGI
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Scavenge { who: self.who }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let mut world_state = SystemState::<(
+            Query<&WorldEntity, Without<Item>>,
+            Query<(Entity, &WorldEntity, &Item), Without<CarriedMarker>>,
+            Query<&WorldEntity, With<PlayerMarker>>,
+            NavigationParams,
+        )>::new(world);
+        let (creatures, items, player, mut navigation) = world_state.get_mut(world);
+
+        let Ok(WorldEntity { position, .. }) = creatures.get(self.who) else {
+            return vec![];
+        };
+
+        let underfoot = items
+            .iter()
+            .filter(|(_, item_world, item)| {
+                item_world.position == *position && worth_taking(item)
+            })
+            .map(|(item_entity, ..)| item_entity)
+            .collect::<Vec<_>>();
+
+        if !underfoot.is_empty() {
+            return vec![a_pickup(self.who, underfoot)];
+        }
+
+        // the item map is kept alongside the ones around the player, so it needs to know where
+        // they are, even if nobody's after them
+        let player = player.get_single().map(|p| p.position).unwrap_or(*position);
+        let from = *position;
+        let close_enough = navigation
+            .steps_to_items(from, player)
+            .map(|steps| steps <= SCAVENGE_RANGE)
+            .unwrap_or_default();
+
+        match navigation.downhill(NavigationGoal::Items, from, player) {
+            Some(step) if close_enough => vec![a_move(self.who, step - from)],
+            _ => vec![a_random_walk(self.who)],
+        }
+    }
+}
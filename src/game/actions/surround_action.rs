@@ -0,0 +1,77 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use crate::game::{
+    actions::{a_melee, a_move, a_track},
+    character::CharacterStat,
+    grid::WorldEntity,
+    navigation::{is_walkable, NavigationParams},
+};
+
+use super::{AbstractAction, Action, ActionKind, ActionResult};
+
+// closes in on `target` from whichever side next to them is still free, so that a pack
+// ends up all around them instead of queueing up behind each other
+#[derive(Debug)]
+pub struct SurroundAction {
+    pub who: Entity,
+    pub target: Entity,
+}
+
+pub fn a_surround(who: Entity, target: Entity) -> AbstractAction {
+    Box::new(SurroundAction { who, target })
+}
+
+impl Action for SurroundAction {
+    fn get_affiliated_stat(&self) -> CharacterStat {
+        CharacterStat::AGI
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::Surround {
+            who: self.who,
+            target: self.target,
+        }
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let mut world_state = SystemState::<(Query<&WorldEntity>, NavigationParams)>::new(world);
+        let (world_state_query, mut navigation) = world_state.get_mut(world);
+
+        let Ok(WorldEntity { position, .. }) = world_state_query.get(self.who) else {
+            return vec![];
+        };
+
+        let Ok(WorldEntity {
+            position: target_position,
+            ..
+        }) = world_state_query.get(self.target)
+        else {
+            return vec![];
+        };
+
+        let (from, target) = (*position, *target_position);
+        let offset = target - from;
+        if offset.x.abs() <= 1 && offset.y.abs() <= 1 {
+            return vec![a_melee(self.who, offset)];
+        }
+
+        let distance = |tile: IVec2| {
+            let d = (tile - from).abs();
+            d.x.max(d.y)
+        };
+
+        let flank = (-1..=1)
+            .flat_map(|x| (-1..=1).map(move |y| target + IVec2::new(x, y)))
+            .filter(|tile| {
+                *tile != target
+                    && is_walkable(&navigation.grid, &navigation.world_data, *tile)
+                    && !navigation.world_data.blocking.contains_key(tile)
+            })
+            .min_by_key(|tile| distance(*tile));
+
+        match flank.and_then(|tile| navigation.next_step(from, tile)) {
+            Some(step) => vec![a_move(self.who, step - from)],
+            None => vec![a_track(self.who, self.target)],
+        }
+    }
+}
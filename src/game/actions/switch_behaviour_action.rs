@@ -19,6 +19,13 @@ impl Action for SwitchBehaviourAction {
         CharacterStat::INT
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Behave {
+            entity: self.entity,
+            behaviour: self.behaviour,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut ai_agent_state = SystemState::<Query<&mut AIAgent>>::new(world);
         let mut ai_agent_query = ai_agent_state.get_mut(world);
@@ -1,6 +1,11 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
-use crate::game::grid::{Grid, WorldEntity};
+use crate::game::{
+    fov::RecalculateFOVEvent,
+    grid::{Grid, WorldData, WorldEntity},
+    history::{args, HistoryLog},
+    outcomes::{report, ActionOutcome},
+};
 
 use super::*;
 
@@ -22,23 +27,72 @@ impl Action for TeleportAction {
         CharacterStat::AGI
     }
 
-    fn do_action(&mut self, world: &mut World) -> ActionResult {
-        let mut read_system_state =
-            SystemState::<(Res<Grid>, Query<(&mut WorldEntity, &mut Transform)>)>::new(world);
+    fn kind(&self) -> ActionKind {
+        ActionKind::Teleport {
+            who: self.entity,
+            wher: self.place,
+        }
+    }
+
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        if world.get::<WorldEntity>(self.entity).is_none() {
+            return Err(ActionRejection::Gone);
+        }
 
-        let (grid, mut world_entities) = read_system_state.get_mut(world);
+        let world_data = world.resource::<WorldData>();
+        let taken = world_data
+            .blocking
+            .get(&self.place)
+            .is_some_and(|other| *other != self.entity);
+        if world_data.solid.contains(&self.place) || taken {
+            return Err(ActionRejection::Blocked);
+        }
+
+        Ok(())
+    }
+
+    fn do_action(&self, world: &mut World) -> ActionResult {
+        let mut read_system_state = SystemState::<(
+            Res<Grid>,
+            ResMut<WorldData>,
+            ResMut<HistoryLog>,
+            Query<(&mut WorldEntity, &mut Transform)>,
+            EventWriter<RecalculateFOVEvent>,
+        )>::new(world);
+
+        let (grid, mut world_data, mut log, mut world_entities, mut fov_events) =
+            read_system_state.get_mut(world);
 
         let Ok((mut world_entity, mut transform)) = world_entities.get_mut(self.entity) else {
             return vec![];
         };
 
-        let next_position = self.place;
-        let mut new_transform = grid.get_tile_position(next_position);
-        new_transform.translation.z = transform.translation.z;
+        let from = world_entity.position;
+        world_entity.position = self.place;
+        if world_entity.blocking {
+            if world_data.blocking.get(&from) == Some(&self.entity) {
+                world_data.blocking.remove(&from);
+            }
+            world_data.blocking.insert(self.place, self.entity);
+        }
 
+        // no walking over there, it just happens
+        let mut new_transform = grid.get_tile_position(self.place);
+        new_transform.translation.z = transform.translation.z;
         *transform = new_transform;
-        world_entity.position = self.place;
 
+        if world_entity.is_player {
+            fov_events.send(RecalculateFOVEvent);
+        }
+
+        log.say("teleport", args().subject(&world_entity));
+
+        let teleported = ActionOutcome::Teleported {
+            who: self.entity,
+            from,
+            to: self.place,
+        };
+        report(world, teleported);
         vec![]
     }
 }
@@ -0,0 +1,940 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use doryen_fov::MapData;
+
+use crate::game::{
+    ai::{
+        awareness::{can_see, stealth, Awareness},
+        factions::{share_awareness, Faction},
+        utility::{ai_utility, UtilityProfile, UtilityScores},
+        AIStrategy, PendingActions,
+    },
+    character::{Character, CharacterStat},
+    feel::{Random, RunSeed},
+    fov::RecalculateFOVEvent,
+    grid::{Grid, WorldData, WorldEntity},
+    health::{Health, HitPoint},
+    history::HistoryLog,
+    inventory::{
+        CarriedItems, CarriedMarker, CurrentlySelectedItem, EquippedItems, Item, ItemCatalog,
+        ItemType,
+    },
+    magic::{shorthand, Focus, Magic, MagicAspect},
+    mobs::MonsterCatalog,
+    navigation::{Navigation, CASTER_RANGE},
+    noise::{hear_noises, Noise, NoiseEvent},
+    outcomes::{tally_outcomes, ActionOutcome, RunStatistics},
+    player::{Achievements, PlayerState},
+    procgen::{LevelDepth, MapRadius, PlayerMarker},
+    spells::{floor_items_near, Spell, DISINTEGRATE_RANGE},
+    status::{inflict, is_afflicted, StatusEffect, StatusEffects},
+    turns::{TurnCounter, TurnOrder, TurnTaker},
+};
+
+use super::*;
+
+// the smallest world the actions can run in: no app, no schedules, no assets
+pub(crate) fn test_world() -> World {
+    let mut world = World::new();
+    world.insert_resource(Grid {
+        size: IVec2::new(120, 62),
+        tile: IVec2::new(16, 16),
+        atlas: Handle::default(),
+        entities: Default::default(),
+    });
+    world.insert_resource(WorldData {
+        data: MapData::new(122, 64),
+        solid: Default::default(),
+        memory: Default::default(),
+        blocking: Default::default(),
+    });
+    world.insert_resource(HistoryLog::default());
+    world.insert_resource(Random::seeded(1337));
+    world.insert_resource(LevelDepth(1, 0));
+    world.init_resource::<TurnOrder>();
+    world.init_resource::<PlayerState>();
+    world.init_resource::<ItemCatalog>();
+    world.init_resource::<Navigation>();
+    world.init_resource::<Events<ActionEvent>>();
+    world.init_resource::<Events<RecalculateFOVEvent>>();
+    world.init_resource::<Events<NoiseEvent>>();
+    world.init_resource::<Events<ActionOutcome>>();
+    world.init_resource::<RunStatistics>();
+    world
+}
+
+pub(crate) fn spawn_fighter(
+    world: &mut World,
+    name: &str,
+    position: IVec2,
+    character: Character,
+    hp: usize,
+) -> Entity {
+    let is_player = name == "You";
+    let mut fighter = world.spawn((
+        WorldEntity {
+            name: name.to_string(),
+            position,
+            sprite_index: 0,
+            blocking: true,
+            is_player,
+        },
+        Transform::default(),
+        character,
+        Health::new(hp),
+        Focus(0),
+        CarriedItems::default(),
+        EquippedItems::default(),
+    ));
+
+    if is_player {
+        fighter.insert(PlayerMarker);
+    }
+
+    let entity = fighter.id();
+    world
+        .resource_mut::<WorldData>()
+        .blocking
+        .insert(position, entity);
+    entity
+}
+
+// a strong player always does 3 damage, and a target without agility never dodges
+pub(crate) fn strong() -> Character {
+    Character {
+        strength: 9,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn clumsy() -> Character {
+    Character {
+        strength: 5,
+        agility: 0,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn run(world: &mut World, action: AbstractAction) {
+    world.send_event(ActionEvent(action));
+    handle_gameplay_action(world);
+}
+
+#[test]
+fn moving_into_a_blocker_becomes_a_melee_attack() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 10);
+
+    let reactions = a_move(player, IVec2::X).do_action(&mut world);
+
+    assert_eq!(reactions.len(), 1);
+    assert!(matches!(
+        reactions[0].kind(),
+        ActionKind::Melee { entity, direction } if entity == player && direction == IVec2::X
+    ));
+}
+
+#[test]
+fn moving_into_a_blocker_hits_it_instead_of_moving() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 10);
+
+    run(&mut world, a_move(player, IVec2::X));
+
+    assert_eq!(world.get::<WorldEntity>(player).unwrap().position, IVec2::ZERO);
+    assert_eq!(world.get::<Health>(goblin).unwrap().hitpoints.len(), 7);
+    assert!(world
+        .resource::<HistoryLog>()
+        .lines
+        .contains(&"You do 3 damage to goblin.".to_string()));
+}
+
+#[test]
+fn moving_into_an_empty_tile_moves() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+
+    run(&mut world, a_move(player, IVec2::Y));
+
+    assert_eq!(world.get::<WorldEntity>(player).unwrap().position, IVec2::Y);
+    let world_data = world.resource::<WorldData>();
+    assert_eq!(world_data.blocking.get(&IVec2::Y), Some(&player));
+    assert!(!world_data.blocking.contains_key(&IVec2::ZERO));
+}
+
+// walkable tiles are the ones the grid has, so lay some down
+fn lay_floor(world: &mut World, radius: i32) {
+    for x in -radius..=radius {
+        for y in -radius..=radius {
+            let tile = world.spawn_empty().id();
+            world
+                .resource_mut::<Grid>()
+                .entities
+                .insert(IVec2::new(x, y), tile);
+        }
+    }
+}
+
+// lets light through everywhere `lay_floor` puts a tile, apart from `walls`
+fn light(world: &mut World, radius: i32, walls: &[IVec2]) {
+    world.resource_scope(|world, mut world_data: Mut<WorldData>| {
+        let grid = world.resource::<Grid>();
+        for x in -radius..=radius {
+            for y in -radius..=radius {
+                let (nx, ny) = grid.norm(IVec2::new(x, y));
+                let open = !walls.contains(&IVec2::new(x, y));
+                world_data.data.set_transparent(nx, ny, open);
+            }
+        }
+    });
+}
+
+#[test]
+fn tracking_walks_around_walls() {
+    let mut world = test_world();
+    lay_floor(&mut world, 5);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::ZERO, clumsy(), 10);
+    let player = spawn_fighter(&mut world, "You", IVec2::new(4, 0), strong(), 10);
+    for y in -1..=1 {
+        world.resource_mut::<WorldData>().solid.insert(IVec2::new(1, y));
+    }
+
+    let reactions = a_track(goblin, player).do_action(&mut world);
+
+    assert_eq!(reactions.len(), 1);
+    assert!(matches!(
+        reactions[0].kind(),
+        ActionKind::Move { entity, direction }
+            if entity == goblin && direction.x == 0 && direction.y.abs() == 1
+    ));
+}
+
+#[test]
+fn fleeing_backs_away_from_the_threat() {
+    let mut world = test_world();
+    lay_floor(&mut world, 5);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::ZERO, clumsy(), 10);
+    let player = spawn_fighter(&mut world, "You", IVec2::new(2, 0), strong(), 10);
+
+    let reactions = a_flee(goblin, player).do_action(&mut world);
+
+    assert_eq!(reactions.len(), 1);
+    assert!(matches!(
+        reactions[0].kind(),
+        ActionKind::Move { entity, direction } if entity == goblin && direction.x == -1
+    ));
+}
+
+#[test]
+fn kiting_backs_off_to_casting_range() {
+    let mut world = test_world();
+    lay_floor(&mut world, 10);
+    let caster = spawn_fighter(&mut world, "Evoker", IVec2::ZERO, clumsy(), 10);
+    let player = spawn_fighter(&mut world, "You", IVec2::new(2, 0), strong(), 10);
+
+    let reactions = a_kite(caster, player).do_action(&mut world);
+
+    assert_eq!(reactions.len(), 1);
+    assert!(matches!(
+        reactions[0].kind(),
+        ActionKind::Move { entity, direction } if entity == caster && direction.x == -1
+    ));
+}
+
+#[test]
+fn kiting_stays_put_at_casting_range() {
+    let mut world = test_world();
+    lay_floor(&mut world, 10);
+    let caster = spawn_fighter(&mut world, "Evoker", IVec2::ZERO, clumsy(), 10);
+    let player = spawn_fighter(&mut world, "You", IVec2::new(CASTER_RANGE, 0), strong(), 10);
+
+    assert!(a_kite(caster, player).do_action(&mut world).is_empty());
+}
+
+#[test]
+fn killing_hit_chains_death_then_bones() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+
+    let reactions = a_hit(player, goblin).do_action(&mut world);
+    assert_eq!(reactions.len(), 1);
+    assert!(matches!(reactions[0].kind(), ActionKind::Death { entity } if entity == goblin));
+
+    let reactions = reactions[0].do_action(&mut world);
+    assert_eq!(reactions.len(), 1);
+    assert!(matches!(
+        reactions[0].kind(),
+        ActionKind::LeaveBones { stats, pos }
+            if stats == vec![
+                (CharacterStat::STR, 1),
+                (CharacterStat::STR, 1),
+                (CharacterStat::AGI, -1),
+                (CharacterStat::AGI, -1),
+                (CharacterStat::AGI, -1),
+            ] && pos == IVec2::X
+    ));
+}
+
+#[test]
+fn killing_hit_leaves_bones_where_the_target_stood() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+
+    run(&mut world, a_hit(player, goblin));
+
+    assert!(world.get::<Health>(goblin).unwrap().hitpoints.is_empty());
+    assert!(!world.resource::<WorldData>().blocking.contains_key(&IVec2::X));
+    assert_ne!(*world.resource::<PlayerState>(), PlayerState::Dead);
+
+    let mut items = world.query::<(&Item, &WorldEntity)>();
+    let bones = items.iter(&world).collect::<Vec<_>>();
+    assert_eq!(bones.len(), 1);
+
+    // repeated stats collapse into one when the bones are rolled
+    let (item, world_entity) = bones[0];
+    assert_eq!(item.name, "Bones");
+    assert_eq!(world_entity.position, IVec2::X);
+    assert_eq!(item.equip_stat_changes.len(), 2);
+    assert!(item.equip_stat_changes.contains(&(CharacterStat::STR, 1)));
+    assert!(item.equip_stat_changes.contains(&(CharacterStat::AGI, -1)));
+}
+
+pub(crate) fn spawn_item(world: &mut World, owner: Entity, stats: Vec<(CharacterStat, i32)>) -> Entity {
+    let item = world
+        .spawn((
+            Item {
+                name: "Bones".to_string(),
+                image: 0,
+                item_type: ItemType::Artifact,
+                equip_stat_changes: stats,
+                spell: None,
+            },
+            WorldEntity {
+                name: "Bones".to_string(),
+                position: IVec2::ZERO,
+                sprite_index: 0,
+                blocking: false,
+                is_player: false,
+            },
+        ))
+        .id();
+
+    world.get_mut::<CarriedItems>(owner).unwrap().0.push(item);
+    item
+}
+
+#[test]
+fn consuming_enchants_hitpoints_past_the_focus() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, Character::default(), 5);
+    world.get_mut::<Focus>(player).unwrap().0 = 1;
+    let item = spawn_item(
+        &mut world,
+        player,
+        vec![(CharacterStat::STR, 1), (CharacterStat::AGI, -1)],
+    );
+
+    run(&mut world, a_consume(player, item));
+
+    // five hitpoints, one skipped by focus: the rightmost two are hitpoints 3 and 2
+    let stats = world
+        .get::<Health>(player)
+        .unwrap()
+        .hitpoints
+        .iter()
+        .map(|hp| hp.stat)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stats,
+        vec![
+            None,
+            None,
+            Some((CharacterStat::AGI, -1)),
+            Some((CharacterStat::STR, 1)),
+            None
+        ]
+    );
+
+    let character = world.get::<Character>(player).unwrap();
+    assert_eq!(character.strength, 4);
+    assert_eq!(character.agility, 2);
+    assert_eq!(world.get::<Focus>(player).unwrap().0, 0);
+    assert!(world.get_entity(item).is_none());
+    assert!(world.get::<CarriedItems>(player).unwrap().0.is_empty());
+}
+
+#[test]
+fn consuming_too_deep_misses_part_of_the_spell() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, Character::default(), 2);
+    world.get_mut::<Focus>(player).unwrap().0 = 1;
+    let item = spawn_item(
+        &mut world,
+        player,
+        vec![(CharacterStat::STR, 1), (CharacterStat::AGI, 1)],
+    );
+
+    run(&mut world, a_consume(player, item));
+
+    let health = world.get::<Health>(player).unwrap();
+    assert_eq!(health.hitpoints[0].stat, Some((CharacterStat::STR, 1)));
+    assert_eq!(health.hitpoints[1].stat, None);
+    assert_eq!(world.get::<Character>(player).unwrap().agility, 3);
+}
+
+#[test]
+fn descending_without_a_nine_withers_health() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, Character::default(), 10);
+
+    run(&mut world, a_descend(player));
+
+    // the strongest stat is STR at 3, so 6 health goes to the healer
+    let health = world.get::<Health>(player).unwrap();
+    assert_eq!(health.size, 5);
+    assert_eq!(health.hitpoints.len(), 5);
+    assert!(health
+        .hitpoints
+        .iter()
+        .all(|hp| hp.stat == Some((CharacterStat::STR, -1))));
+    assert_eq!(world.get::<Character>(player).unwrap().strength, -2);
+    assert_eq!(world.resource::<LevelDepth>().1, 6);
+}
+
+#[test]
+fn descending_with_a_nine_heals_and_grows_the_weakest_stat() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    world
+        .get_mut::<Health>(player)
+        .unwrap()
+        .hitpoints
+        .truncate(4);
+
+    run(&mut world, a_descend(player));
+
+    let health = world.get::<Health>(player).unwrap();
+    assert_eq!(health.size, 10);
+    assert_eq!(health.hitpoints.len(), 10);
+    assert_eq!(health.hitpoints[0].stat, Some((CharacterStat::ARC, 1)));
+    assert!(health.hitpoints.iter().skip(1).all(|hp| hp.stat.is_none()));
+
+    let character = world.get::<Character>(player).unwrap();
+    assert_eq!(character.strength, 9);
+    assert_eq!(character.arcana, 4);
+    assert_eq!(world.resource::<LevelDepth>().1, 0);
+}
+
+#[test]
+fn hitpoints_enchant_replaces_the_old_stat() {
+    let mut hp = HitPoint {
+        stat: Some((CharacterStat::STR, 1)),
+        ..Default::default()
+    };
+
+    let diff = hp.enchant((CharacterStat::STR, -1));
+
+    assert_eq!(diff.get(&CharacterStat::STR), Some(&-2));
+    assert_eq!(hp.stat, Some((CharacterStat::STR, -1)));
+}
+
+#[test]
+fn monsters_dont_see_through_walls() {
+    let mut world = test_world();
+    let walls = [IVec2::new(1, -1), IVec2::new(1, 0), IVec2::new(1, 1)];
+    light(&mut world, 6, &walls);
+
+    let grid = world.resource::<Grid>();
+    let world_data = world.resource::<WorldData>();
+    assert!(can_see(grid, world_data, IVec2::ZERO, IVec2::new(0, 4), 6));
+    assert!(!can_see(grid, world_data, IVec2::ZERO, IVec2::new(4, 0), 6));
+    assert!(!can_see(grid, world_data, IVec2::ZERO, IVec2::new(0, 5), 3));
+}
+
+#[test]
+fn awareness_follows_what_was_seen() {
+    let here = IVec2::ZERO;
+    let there = IVec2::new(5, 0);
+
+    let glimpsed = Awareness::Unaware.update(Some(there), false, here);
+    assert_eq!(glimpsed, Awareness::Suspicious { at: there, turns: 0 });
+
+    let hunting = glimpsed.update(Some(there), false, here);
+    assert_eq!(hunting, Awareness::Hunting { last_seen: there });
+
+    let lost = hunting.update(None, false, here);
+    assert_eq!(lost, Awareness::LostTrack { last_seen: there, turns: 0 });
+
+    // getting to where the player was, and not finding them there, is the end of it
+    assert_eq!(lost.update(None, false, there), Awareness::Unaware);
+}
+
+#[test]
+fn a_yell_calls_over_whoever_hears_it() {
+    let mut world = test_world();
+    lay_floor(&mut world, 8);
+    let healer = spawn_fighter(&mut world, "Healer", IVec2::ZERO, strong(), 10);
+    let near = spawn_fighter(&mut world, "Goblin", IVec2::new(5, 0), clumsy(), 2);
+    let walled_off = spawn_fighter(&mut world, "Goblin", IVec2::new(7, 0), clumsy(), 2);
+    world.entity_mut(near).insert(Awareness::default());
+    world.entity_mut(walled_off).insert(Awareness::default());
+    for y in -8..=8 {
+        world.resource_mut::<WorldData>().solid.insert(IVec2::new(6, y));
+    }
+
+    run(&mut world, a_yell(healer));
+    world.run_system_once(hear_noises);
+
+    assert_eq!(
+        *world.get::<Awareness>(near).unwrap(),
+        Awareness::Suspicious {
+            at: IVec2::ZERO,
+            turns: 0
+        }
+    );
+    assert_eq!(*world.get::<Awareness>(walled_off).unwrap(), Awareness::Unaware);
+}
+
+#[test]
+fn the_nimble_step_lightly() {
+    let mut nimble = clumsy();
+    nimble.agility = 9;
+
+    assert_eq!(Noise::Step.loudness(Some(&clumsy())), 5);
+    assert_eq!(Noise::Step.loudness(Some(&nimble)), 1);
+}
+
+#[test]
+fn sneak_attacks_hit_twice_as_hard_and_wake_the_target() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 10);
+    world.entity_mut(goblin).insert(Awareness::Unaware);
+
+    run(&mut world, a_hit(player, goblin));
+
+    assert_eq!(world.get::<Health>(goblin).unwrap().hitpoints.len(), 4);
+    assert!(world
+        .resource::<HistoryLog>()
+        .lines
+        .contains(&"You catch goblin unawares!".to_string()));
+    assert!(world.get::<Awareness>(goblin).unwrap().has_noticed());
+
+    run(&mut world, a_hit(player, goblin));
+
+    assert_eq!(world.get::<Health>(goblin).unwrap().hitpoints.len(), 1);
+}
+
+#[test]
+fn cover_makes_anyone_stealthier() {
+    let sneaky = Character {
+        agility: 6,
+        intelligence: 6,
+        ..Default::default()
+    };
+
+    assert_eq!(stealth(&clumsy(), false), 0);
+    assert_eq!(stealth(&clumsy(), true), 2);
+    assert_eq!(stealth(&sneaky, false), 3);
+}
+
+#[test]
+fn brutes_fight_up_close_and_run_when_hurt_and_alone() {
+    let mut world = test_world();
+    world.init_resource::<MonsterCatalog>();
+    lay_floor(&mut world, 6);
+    light(&mut world, 6, &[]);
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 10);
+    world.entity_mut(goblin).insert(UtilityProfile("brute".to_string()));
+
+    let plan = ai_utility().do_thinking(goblin, &mut world);
+    assert!(matches!(
+        plan[0].kind(),
+        ActionKind::Track { who, target } if who == goblin && target == player
+    ));
+
+    world.get_mut::<Health>(goblin).unwrap().hitpoints.truncate(2);
+    world.get_mut::<WorldEntity>(goblin).unwrap().position = IVec2::new(4, 0);
+
+    let plan = ai_utility().do_thinking(goblin, &mut world);
+    assert!(matches!(
+        plan[0].kind(),
+        ActionKind::Flee { who, target } if who == goblin && target == player
+    ));
+    // every plan in the profile gets a score, whether it won or not
+    let brute = world.resource::<MonsterCatalog>().utility["brute"].len();
+    assert_eq!(world.get::<UtilityScores>(goblin).unwrap().0.len(), brute);
+}
+
+#[test]
+fn rival_factions_fight_and_allies_dont() {
+    let mut world = test_world();
+    let bat = spawn_fighter(&mut world, "Bat", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 10);
+    let other_goblin = spawn_fighter(&mut world, "Goblin", IVec2::new(2, 0), strong(), 10);
+    world.entity_mut(bat).insert(Faction::Beasts);
+    world.entity_mut(goblin).insert(Faction::Goblins);
+    world.entity_mut(other_goblin).insert(Faction::Goblins);
+
+    run(&mut world, a_melee(other_goblin, -IVec2::X));
+    assert_eq!(world.get::<Health>(goblin).unwrap().hitpoints.len(), 10);
+
+    run(&mut world, a_melee(bat, IVec2::X));
+    assert_eq!(world.get::<Health>(goblin).unwrap().hitpoints.len(), 7);
+}
+
+#[test]
+fn monsters_tell_their_faction_where_the_player_is() {
+    let mut world = test_world();
+    let spotter = spawn_fighter(&mut world, "Goblin", IVec2::ZERO, clumsy(), 2);
+    let near = spawn_fighter(&mut world, "Goblin", IVec2::new(5, 0), clumsy(), 2);
+    let far = spawn_fighter(&mut world, "Goblin", IVec2::new(12, 0), clumsy(), 2);
+    let rival = spawn_fighter(&mut world, "Orc", IVec2::new(0, 3), clumsy(), 2);
+    let seen = IVec2::new(-3, 0);
+    world.entity_mut(spotter).insert((Faction::Goblins, Awareness::Hunting { last_seen: seen }));
+    world.entity_mut(near).insert((Faction::Goblins, Awareness::Unaware));
+    world.entity_mut(far).insert((Faction::Goblins, Awareness::Unaware));
+    world.entity_mut(rival).insert((Faction::Cult, Awareness::Unaware));
+
+    share_awareness(&mut world, spotter);
+
+    assert_eq!(
+        *world.get::<Awareness>(near).unwrap(),
+        Awareness::LostTrack {
+            last_seen: seen,
+            turns: 0
+        }
+    );
+    assert_eq!(*world.get::<Awareness>(far).unwrap(), Awareness::Unaware);
+    assert_eq!(*world.get::<Awareness>(rival).unwrap(), Awareness::Unaware);
+}
+
+#[test]
+fn monsters_drop_what_they_carry_when_they_die() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+    world.entity_mut(goblin).insert(TurnTaker);
+
+    let item = spawn_item(&mut world, goblin, vec![(CharacterStat::STR, 1)]);
+    world.entity_mut(item).insert((CarriedMarker, Transform::default(), Visibility::Hidden));
+
+    run(&mut world, a_hit(player, goblin));
+
+    assert!(world.get::<CarriedMarker>(item).is_none());
+    assert_eq!(*world.get::<Visibility>(item).unwrap(), Visibility::Visible);
+    assert_eq!(world.get::<WorldEntity>(item).unwrap().position, IVec2::X);
+}
+
+#[test]
+fn aspects_wake_up_once_both_their_stats_reach_the_threshold() {
+    let magic = Magic {
+        aspects: [(shorthand(CharacterStat::STR, CharacterStat::AGI), MagicAspect::Edge)]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    };
+
+    let old = Character {
+        strength: 6,
+        agility: 5,
+        ..Default::default()
+    };
+    let new = Character {
+        agility: 6,
+        ..old.clone()
+    };
+
+    assert!(magic.awakened(&old, &old).is_empty());
+    assert_eq!(
+        magic.awakened(&old, &new),
+        vec![(MagicAspect::Edge, [CharacterStat::STR, CharacterStat::AGI])]
+    );
+    assert!(magic.awakened(&new, &new).is_empty());
+}
+
+#[test]
+fn dust_steals_health_from_whoever_is_close() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, Character::default(), 5);
+    let near = spawn_fighter(&mut world, "Goblin", IVec2::X, Character::default(), 3);
+    let far = spawn_fighter(&mut world, "Orc", IVec2::new(5, 0), Character::default(), 3);
+    world.get_mut::<Health>(player).unwrap().hitpoints.pop_back();
+
+    run(&mut world, a_dust(player));
+
+    assert_eq!(world.get::<Health>(player).unwrap().hitpoints.len(), 5);
+    assert_eq!(world.get::<Health>(near).unwrap().hitpoints.len(), 2);
+    assert_eq!(world.get::<Health>(far).unwrap().hitpoints.len(), 3);
+}
+
+#[test]
+fn the_rooted_cant_move_but_can_still_fight() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 5);
+    inflict(&mut world, player, StatusEffect::Rooted, 2);
+
+    run(&mut world, a_move(player, IVec2::Y));
+    assert_eq!(world.get::<WorldEntity>(player).unwrap().position, IVec2::ZERO);
+
+    run(&mut world, a_move(player, IVec2::X));
+    assert_eq!(world.get::<Health>(goblin).unwrap().hitpoints.len(), 2);
+}
+
+#[test]
+fn effects_end_with_their_time_or_their_hitpoint() {
+    let mut effects = StatusEffects::default();
+    effects.add(StatusEffect::Burning, 2);
+    effects.add(StatusEffect::Burning, 1);
+    assert!(effects.tick().is_empty());
+    assert_eq!(effects.tick(), vec![StatusEffect::Burning]);
+
+    let mut world = test_world();
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::ZERO, clumsy(), 3);
+    world.get_mut::<Health>(goblin).unwrap().hitpoints[2].status = Some(StatusEffect::Bleeding);
+    assert!(is_afflicted(&world, goblin, StatusEffect::Bleeding));
+
+    world.get_mut::<Health>(goblin).unwrap().normal_damage(1);
+    assert!(!is_afflicted(&world, goblin, StatusEffect::Bleeding));
+}
+
+#[test]
+fn impossible_actions_are_rejected_without_costing_time() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    world.resource_mut::<WorldData>().solid.insert(IVec2::X);
+    world.resource_mut::<TurnOrder>().insert(player, 0);
+
+    assert!(!submit_action(&mut world, a_move(player, IVec2::X)));
+    assert_eq!(world.resource::<TurnOrder>().now, 0);
+    assert!(world
+        .resource::<HistoryLog>()
+        .lines
+        .contains(&"There's a wall in the way.".to_string()));
+
+    let items = (0..3)
+        .map(|_| spawn_item(&mut world, player, vec![]))
+        .collect::<Vec<_>>();
+    world.get_mut::<EquippedItems>(player).unwrap().0 = items[..2].to_vec();
+    assert_eq!(a_equip(player, items[2]).validate(&world), Err(ActionRejection::HandsFull));
+
+    let throw = |item: Entity, at: IVec2| a_throw(player, item, at).validate(&world);
+    assert_eq!(throw(items[0], IVec2::Y), Err(ActionRejection::Equipped));
+    assert_eq!(throw(items[2], IVec2::new(30, 0)), Err(ActionRejection::OutOfRange));
+    assert_eq!(throw(items[2], IVec2::Y), Ok(()));
+
+    assert!(submit_action(&mut world, a_move(player, IVec2::Y)));
+    assert!(world.resource::<TurnOrder>().now > 0);
+}
+
+#[test]
+fn monsters_drop_plans_that_fall_through() {
+    let mut world = test_world();
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::ZERO, clumsy(), 3);
+    world.resource_mut::<WorldData>().solid.insert(IVec2::X);
+    world
+        .entity_mut(goblin)
+        .insert(PendingActions(VecDeque::from([a_wait(goblin), a_wait(goblin)])));
+
+    run(&mut world, a_move(goblin, IVec2::X));
+
+    assert_eq!(world.get::<WorldEntity>(goblin).unwrap().position, IVec2::ZERO);
+    assert!(world.get::<PendingActions>(goblin).unwrap().0.is_empty());
+    assert!(world.resource::<HistoryLog>().lines.is_empty());
+}
+
+#[test]
+fn actions_report_what_came_of_them() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+
+    run(&mut world, a_hit(player, goblin));
+
+    let outcomes = world
+        .resource_mut::<Events<ActionOutcome>>()
+        .drain()
+        .collect::<Vec<_>>();
+    assert!(outcomes.contains(&ActionOutcome::Damaged {
+        attacker: player,
+        target: goblin,
+        amount: 3,
+        sneak: false,
+    }));
+    assert!(outcomes.contains(&ActionOutcome::Killed {
+        who: goblin,
+        player: false,
+    }));
+
+    world.resource_mut::<Events<ActionOutcome>>().extend(outcomes);
+    world.run_system_once(tally_outcomes);
+
+    let statistics = world.resource::<RunStatistics>();
+    assert_eq!(statistics.damage_dealt, 3);
+    assert_eq!(statistics.fallen, 1);
+}
+
+#[test]
+fn spells_move_people_and_destroy_only_what_lies_on_the_floor() {
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+
+    assert_eq!(a_teleport(player, IVec2::X).validate(&world), Err(ActionRejection::Blocked));
+    run(&mut world, a_teleport(player, IVec2::new(3, 3)));
+
+    assert_eq!(world.get::<WorldEntity>(player).unwrap().position, IVec2::new(3, 3));
+    let blocking = &world.resource::<WorldData>().blocking;
+    assert_eq!(blocking.get(&IVec2::new(3, 3)), Some(&player));
+    assert_eq!(blocking.get(&IVec2::X), Some(&goblin));
+    assert!(!blocking.contains_key(&IVec2::ZERO));
+
+    // whoever doesn't block leaves the tile's blocker be
+    let wisp = spawn_item(&mut world, player, vec![]);
+    world.entity_mut(wisp).insert(Transform::default());
+    world.get_mut::<WorldEntity>(wisp).unwrap().position = IVec2::X;
+    run(&mut world, a_teleport(wisp, IVec2::new(-3, 0)));
+    assert_eq!(world.resource::<WorldData>().blocking.get(&IVec2::X), Some(&goblin));
+    world.get_mut::<CarriedItems>(player).unwrap().0.retain(|e| *e != wisp);
+
+    let carried = spawn_item(&mut world, player, vec![]);
+    world.entity_mut(carried).insert(CarriedMarker);
+    let dropped = spawn_item(&mut world, player, vec![]);
+    world.get_mut::<CarriedItems>(player).unwrap().0.retain(|e| *e != dropped);
+
+    let near = floor_items_near(&mut world, IVec2::ZERO, DISINTEGRATE_RANGE);
+    assert_eq!(near, vec![dropped]);
+    assert_eq!(a_desintegrate(carried).validate(&world), Err(ActionRejection::Gone));
+
+    run(&mut world, a_desintegrate(dropped));
+    assert!(world.get_entity(dropped).is_none());
+    assert!(world.get_entity(carried).is_some());
+}
+
+#[test]
+fn afflictions_come_from_items_eaten_or_thrown() {
+    // burning and bleeding come from the aspects, everything else from something lying around
+    let catalog = ItemCatalog::builtin();
+    for effect in [
+        StatusEffect::Rooted,
+        StatusEffect::Blinded,
+        StatusEffect::Hasted,
+        StatusEffect::Silenced,
+    ] {
+        let spell = Some(Spell::Afflict(effect));
+        assert!(catalog.templates.values().any(|t| t.spell == spell), "{:?}", effect);
+    }
+
+    let mut world = test_world();
+    let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+    let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+    let afflicting = |world: &mut World, effect: StatusEffect| {
+        let item = spawn_item(world, player, vec![]);
+        world.entity_mut(item).insert((Transform::default(), Visibility::Hidden));
+        world.get_mut::<Item>(item).unwrap().spell = Some(Spell::Afflict(effect));
+        item
+    };
+
+    let quicksilver = afflicting(&mut world, StatusEffect::Hasted);
+    run(&mut world, a_consume(player, quicksilver));
+    assert!(is_afflicted(&world, player, StatusEffect::Hasted));
+
+    // only those taking turns can throw
+    world.entity_mut(player).insert(TurnTaker);
+    let salt = afflicting(&mut world, StatusEffect::Silenced);
+    run(&mut world, a_throw(player, salt, IVec2::X));
+    assert!(is_afflicted(&world, goblin, StatusEffect::Silenced));
+    assert!(!is_afflicted(&world, player, StatusEffect::Silenced));
+}
+
+// what capturing and restoring a run needs on top of what the actions need
+pub(crate) fn save_world() -> World {
+    let mut world = test_world();
+    world.insert_resource(MapRadius(800));
+    world.init_resource::<RunSeed>();
+    world.init_resource::<TurnCounter>();
+    world.init_resource::<Magic>();
+    world.init_resource::<Achievements>();
+    world.init_resource::<CurrentlySelectedItem>();
+    world
+}
+
+#[test]
+fn action_kinds_survive_saving_and_know_all_their_entities() {
+    let (a, b, c) = (Entity::from_raw(1), Entity::from_raw(2), Entity::from_raw(3));
+    let artifact = Item {
+        name: "Bones".to_string(),
+        image: 0,
+        item_type: ItemType::Artifact,
+        equip_stat_changes: vec![(CharacterStat::ARC, 2)],
+        spell: None,
+    };
+
+    // every kind there is, with how many entities it refers to
+    let hit = ActionKind::Hit {
+        attacker: a,
+        target: b,
+    };
+    let kinds = vec![
+        (ActionKind::Think { entity: a, strategy: AIStrategy::Utility }, 1),
+        (ActionKind::Break { what: a }, 1),
+        (ActionKind::Consume { who: a, what: b }, 2),
+        (ActionKind::Death { entity: a }, 1),
+        (ActionKind::Delay { delay: 150, action: Box::new(hit) }, 2),
+        (ActionKind::Descend { who: a }, 1),
+        (ActionKind::Desintegrate { what: a }, 1),
+        (ActionKind::Destroy { what: a }, 1),
+        (ActionKind::Drop { who: a, what: vec![b, c] }, 3),
+        (ActionKind::Dust { who: a }, 1),
+        (ActionKind::Edge { who: a }, 1),
+        (ActionKind::Equip { who: a, what: b }, 2),
+        (ActionKind::Flee { who: a, target: b }, 2),
+        (ActionKind::Fly { what: a, path: vec![IVec2::X, IVec2::Y], already_flying: true }, 1),
+        (ActionKind::Focus { who: a }, 1),
+        (ActionKind::Fortune { who: a, what: b }, 2),
+        (ActionKind::Heal { entity: a }, 1),
+        (ActionKind::Hit { attacker: a, target: b }, 2),
+        (ActionKind::Inflict { who: a, target: b, artifact }, 2),
+        (ActionKind::Investigate { who: a, at: IVec2::X }, 1),
+        (ActionKind::Kite { who: a, target: b }, 2),
+        (ActionKind::LeaveBones { stats: vec![(CharacterStat::STR, 1)], pos: IVec2::X }, 0),
+        (ActionKind::Lore { who: a, stats: vec![CharacterStat::INT] }, 1),
+        (ActionKind::Melee { entity: a, direction: IVec2::X }, 1),
+        (ActionKind::Move { entity: a, direction: IVec2::X }, 1),
+        (ActionKind::Pickup { who: a, what: vec![b, c] }, 3),
+        (ActionKind::Prison { who: a }, 1),
+        (ActionKind::RandomWalk { who: a }, 1),
+        (ActionKind::Regalia { who: a }, 1),
+        (ActionKind::Sacrifice { who: a }, 1),
+        (ActionKind::Behave { entity: a, behaviour: AIStrategy::Aggro }, 1),
+        (ActionKind::Throw { who: a, what: b, wher: IVec2::X }, 2),
+        (ActionKind::Scavenge { who: a }, 1),
+        (ActionKind::Surround { who: a, target: b }, 2),
+        (ActionKind::Teleport { who: a, wher: IVec2::X }, 1),
+        (ActionKind::Track { who: a, target: b }, 2),
+        (ActionKind::Unequip { who: a, what: b }, 2),
+        (ActionKind::Wait { who: a }, 1),
+        (ActionKind::Yell { who: a }, 1),
+    ];
+
+    for (kind, entities) in kinds {
+        let saved = ron::to_string(&kind).unwrap();
+        let loaded = ron::from_str::<ActionKind>(&saved).unwrap();
+        assert_eq!(ron::to_string(&loaded.into_action().kind()).unwrap(), saved);
+
+        let mut mapped = kind.clone();
+        mapped.map_entities(&mut |_| Entity::PLACEHOLDER);
+        let mut seen = vec![];
+        mapped.map_entities(&mut |e| {
+            seen.push(e);
+            e
+        });
+        assert_eq!(seen.len(), entities, "{:?}", kind);
+        assert!(seen.iter().all(|e| *e == Entity::PLACEHOLDER), "{:?}", kind);
+    }
+}
@@ -5,13 +5,13 @@ use crate::game::{
     actions::a_fly,
     character::{Character, CharacterStat},
     grid::{Grid, WorldEntity},
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::{CarriedItems, CarriedMarker, EquippedItems, Item},
     procgen::ClearLevel,
     turns::TurnTaker,
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionKind, ActionRejection, ActionResult};
 
 #[derive(Debug)]
 pub struct ThrowAction {
@@ -24,11 +24,49 @@ pub fn a_throw(who: Entity, what: Entity, wher: IVec2) -> AbstractAction {
     Box::new(ThrowAction { who, what, wher })
 }
 
+// how many tiles away someone can throw things
+pub fn throw_range(character: &Character) -> i32 {
+    let range = (character.strength.min(5) + character.willpower.min(5)) as f32 * 2.5;
+    (range.ceil() as i32).max(0)
+}
+
 impl Action for ThrowAction {
     fn get_affiliated_stat(&self) -> CharacterStat {
         CharacterStat::STR
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Throw {
+            who: self.who,
+            what: self.what,
+            wher: self.wher,
+        }
+    }
+
+    fn validate(&self, world: &World) -> Result<(), ActionRejection> {
+        let (Some(thrower), Some(character), Some(_)) = (
+            world.get::<WorldEntity>(self.who),
+            world.get::<Character>(self.who),
+            world.get::<Item>(self.what),
+        ) else {
+            return Err(ActionRejection::Gone);
+        };
+
+        if world
+            .get::<EquippedItems>(self.who)
+            .is_some_and(|equipped| equipped.0.contains(&self.what))
+        {
+            return Err(ActionRejection::Equipped);
+        }
+
+        let delta = (self.wher - thrower.position).abs();
+        if delta.x.max(delta.y) > throw_range(character) {
+            return Err(ActionRejection::OutOfRange);
+        }
+
+        Ok(())
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             Query<(&mut WorldEntity, &mut Transform), Without<TurnTaker>>,
@@ -49,14 +87,13 @@ impl Action for ThrowAction {
         let (mut transforms, mut world_entities, mut items, mut log, grid) =
             read_system_state.get_mut(world);
 
-        let Ok((person_entity, Some(character), Some(mut person_carrying), Some(equipped))) =
+        let Ok((person_entity, Some(character), Some(mut person_carrying), Some(_))) =
             world_entities.get_mut(self.who)
         else {
             return vec![];
         };
 
-        let str = character.strength;
-        let will = character.willpower;
+        let max_dist = throw_range(character) as usize + 1;
 
         let mut mark_carried = vec![];
         let item_entity = self.what;
@@ -74,22 +111,14 @@ impl Action for ThrowAction {
         *transform = new_transform;
 
         if let Some(carried_item) = person_carrying.0.iter().position(|i| *i == item_entity) {
-            if equipped.0.contains(&item_entity) {
-                log.add("Cannot throw equipped item. Unequip first.");
-                log.add("");
-                return vec![];
-            }
-
             person_carrying.0.remove(carried_item);
             *vis = Visibility::Visible;
-            log.add(&format!("{} threw {}.", person_entity.name, item.name));
+            log.say("throw", args().subject(person_entity).with("item", &item.name));
             mark_carried.push(item_entity);
         }
 
         let a = person_entity.position;
         let b = self.wher;
-
-        let max_dist = (((str.min(5) + will.min(5)) as f32) * 2.5).ceil() as usize + 1;
         let bres = Bresenham::new((a.x as isize, a.y as isize), (b.x as isize, b.y as isize));
         let mut path = bres
             .into_iter()
@@ -1,11 +1,15 @@
 use bevy::{ecs::system::SystemState, prelude::*};
 
 use crate::game::{
-    actions::a_move, character::CharacterStat, feel::Random, grid::WorldEntity,
+    actions::a_move,
+    character::CharacterStat,
+    feel::Random,
+    grid::WorldEntity,
+    navigation::{NavigationGoal, NavigationParams},
     procgen::PlayerMarker,
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct TrackAction {
@@ -22,13 +26,22 @@ impl Action for TrackAction {
         CharacterStat::WIS
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Track {
+            who: self.who,
+            target: self.target,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut world_state = SystemState::<(
             Query<&WorldEntity>,
             Query<Entity, With<PlayerMarker>>,
             ResMut<Random>,
+            NavigationParams,
         )>::new(world);
-        let (world_state_query, player_entities, mut rng) = world_state.get_mut(world);
+        let (world_state_query, player_entities, mut rng, mut navigation) =
+            world_state.get_mut(world);
 
         let Ok(player_entity) = player_entities.get_single() else {
             return vec![];
@@ -50,6 +63,16 @@ impl Action for TrackAction {
             return vec![];
         };
 
+        // a path around everything in the way, or failing that, at least somewhere closer
+        let (from, to) = (*npc_position, *player_position);
+        if let Some(step) = navigation
+            .next_step(from, to)
+            .or_else(|| navigation.downhill(NavigationGoal::Player, from, to))
+        {
+            return vec![a_move(self.who, step - *npc_position)];
+        }
+
+        // no way through, so just push towards the player and hope for the best
         let dp = *player_position - *npc_position;
         let norm_dp = dp.clamp(IVec2::new(-1, -1), IVec2::new(1, 1));
 
@@ -3,11 +3,11 @@ use bevy::{ecs::system::SystemState, prelude::*};
 use crate::game::{
     character::{Character, CharacterStat},
     grid::WorldEntity,
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::{CarriedItems, EquippedItems, Item},
 };
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct UnequipAction {
@@ -24,6 +24,13 @@ impl Action for UnequipAction {
         CharacterStat::AGI
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Unequip {
+            who: self.who,
+            what: self.what,
+        }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
         let mut read_system_state = SystemState::<(
             ResMut<HistoryLog>,
@@ -45,7 +52,7 @@ impl Action for UnequipAction {
         if let Ok((mut character, world_entity, carried, mut equipped)) =
             world_entity_query.get_mut(self.who)
         {
-            if carried.0.iter().any(|i| *i == self.what) {
+            if carried.0.contains(&self.what) {
                 if let Some(pos) = equipped.0.iter().position(|i| *i == self.what) {
                     equipped.0.remove(pos);
 
@@ -53,7 +60,10 @@ impl Action for UnequipAction {
                         character[*stat] -= *val;
                     }
 
-                    log.add(&format!("{} unequipped {}.", world_entity.name, item.name));
+                    log.say(
+                        "item.unequipped",
+                        args().subject(world_entity).with("item", &item.name),
+                    );
                     log.add("");
                 }
             }
@@ -4,13 +4,15 @@ use bevy::prelude::*;
 
 use crate::game::character::CharacterStat;
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
-pub struct WaitAction;
+pub struct WaitAction {
+    pub who: Entity,
+}
 
-pub fn a_wait() -> AbstractAction {
-    Box::new(WaitAction)
+pub fn a_wait(who: Entity) -> AbstractAction {
+    Box::new(WaitAction { who })
 }
 
 impl Action for WaitAction {
@@ -18,6 +20,10 @@ impl Action for WaitAction {
         CharacterStat::WIL
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Wait { who: self.who }
+    }
+
     fn do_action(&self, _world: &mut World) -> ActionResult {
         thread::sleep(Duration::from_millis(18));
         vec![]
@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 
-use crate::game::{character::CharacterStat, grid::WorldEntity, history::HistoryLog};
+use crate::game::{
+    character::CharacterStat,
+    grid::WorldEntity,
+    history::{args, HistoryLog},
+    noise::{make_noise, Noise},
+    status::{is_afflicted, StatusEffect},
+};
 
-use super::{AbstractAction, Action, ActionResult};
+use super::{AbstractAction, Action, ActionResult, ActionKind};
 
 #[derive(Debug)]
 pub struct YellAction {
@@ -18,15 +24,23 @@ impl Action for YellAction {
         CharacterStat::INT
     }
 
+    fn kind(&self) -> ActionKind {
+        ActionKind::Yell { who: self.who }
+    }
+
     fn do_action(&self, world: &mut World) -> ActionResult {
-        let name = world
-            .get::<WorldEntity>(self.who)
-            .map(|who| who.name.clone())
-            .unwrap_or("someone".to_string());
+        if is_afflicted(world, self.who, StatusEffect::Silenced) {
+            return vec![];
+        }
+
+        let name = world.get::<WorldEntity>(self.who).map(|who| who.name.clone());
         if let Some(mut log) = world.get_resource_mut::<HistoryLog>() {
-            log.add(&format!("You hear {} yell!", name));
+            let name = name.unwrap_or_else(|| log.text("someone", args()));
+            log.say("yell", args().with("subject", name));
             log.add("");
         }
+
+        make_noise(world, self.who, Noise::Yell);
         vec![]
     }
 }
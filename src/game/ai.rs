@@ -1,27 +1,31 @@
 pub mod aggro_ai;
 pub mod aggro_caster;
+pub mod awareness;
 pub mod caster_ai;
+pub mod factions;
+pub mod items;
 pub mod random_move_ai;
 pub mod standard_ai;
 pub mod the_healer_ai;
+pub mod utility;
 
 use std::collections::VecDeque;
 use std::fmt::Debug;
 
 use bevy::{ecs::system::SystemState, prelude::*, utils::HashMap};
-
-use crate::game::turns::TurnOrderEntity;
+use serde::{Deserialize, Serialize};
 
 use self::{
     aggro_ai::ai_aggro, aggro_caster::ai_aggro_caster, caster_ai::ai_caster,
     random_move_ai::ai_random_move, standard_ai::ai_standard, the_healer_ai::ai_the_healer,
+    utility::{ai_utility, debug_utility_scores},
 };
 
 use super::{
-    actions::{a_think, AbstractAction, ActionEvent}, character::Character, grid::WorldEntity, health::Health, mobs::{Mob, TheHealer}, player::PlayerState, procgen::PlayerMarker, turns::{EndTurnEvent, TurnOrder}, GameStates
+    actions::{a_think, submit_action, AbstractAction, ActionEvent}, grid::WorldEntity, health::Health, mobs::TheHealer, player::PlayerState, procgen::PlayerMarker, turns::{TurnOrder, TURN}, GameStates
 };
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum AIStrategy {
     #[default]
     Standard,
@@ -30,6 +34,8 @@ pub enum AIStrategy {
     AggroCaster,
     Caster,
     TheHealer,
+    // scores the plans in the monster's `UtilityProfile` and goes with the best one
+    Utility,
 }
 
 impl From<AIStrategy> for AbstractAIBehaviour {
@@ -41,6 +47,7 @@ impl From<AIStrategy> for AbstractAIBehaviour {
             AIStrategy::AggroCaster => ai_aggro_caster(),
             AIStrategy::Caster => ai_caster(),
             AIStrategy::TheHealer => ai_the_healer(),
+            AIStrategy::Utility => ai_utility(),
         }
     }
 }
@@ -57,78 +64,52 @@ pub trait AIBehaviour: Send + Sync + Debug {
     fn do_thinking(&self, entity: Entity, world: &mut World) -> Vec<AbstractAction>;
 }
 
-pub fn ai_agents_act(
-    mut turn_order: ResMut<TurnOrder>,
-    player_state: Res<PlayerState>,
-    player: Query<(Entity, &WorldEntity), With<PlayerMarker>>,
-    mut non_players: Query<(&Character, &AIAgent, &mut PendingActions), Without<PlayerMarker>>,
-    mut actions: EventWriter<ActionEvent>,
-    mut turn_ended_events: EventWriter<EndTurnEvent>,
-) {
-    let Ok((player_entity, _player_world)) = player.get_single() else {
+pub fn ai_agents_act(world: &mut World) {
+    let Some(player_entity) = get_player(world) else {
         println!("NO PLAYER");
         return;
     };
 
-    let Some(top) = turn_order.peek() else {
-        //println!("TOP ENTITY CAN'T BE PEEKED");
+    // everyone else keeps getting a go until the clock gets to the player. they always get
+    // there, as every action that goes through takes some time, and the only action that
+    // can't go through is a planned one, after which the monster thinks.
+    if !world.resource::<TurnOrder>().contains(player_entity) {
         return;
-    };
+    }
 
-    if player_state.as_ref() == &PlayerState::Dead { return; }
+    while let Some(top) = world.resource::<TurnOrder>().peek() {
+        if top == player_entity {
+            break;
+        }
 
-    if top == player_entity {
-        //println!("TOP ENTITY IS PLAYER");
-        return;
-    }
+        if matches!(
+            world.resource::<PlayerState>(),
+            PlayerState::Dead | PlayerState::EnteringSeed { .. }
+        ) {
+            return;
+        }
 
-    let mut absolute_top = 100;
-    while turn_order.peek() != Some(player_entity) {
-        println!("{:?}", player_state.as_ref());
-        if player_state.as_ref() == &PlayerState::Dead { return; }
-
-        absolute_top -= 1;
-        if absolute_top == 0 { return; }
-
-        if let Some(top) = turn_order.peek() {
-            println!("Turn order: {:?}", top);
-            if let Ok((character, ai_agent, mut pending)) = non_players.get_mut(top) {
-                let current_energy = turn_order
-                    .order
-                    .get_priority(&TurnOrderEntity { entity: top })
-                    .unwrap();
-
-                if current_energy.0 == 0 {
-                    turn_order.restart_turn();
-                    turn_ended_events.send(EndTurnEvent);
-                    return;
-                }
-
-                #[allow(unused_assignments)]
-                let mut taken_action: Option<ActionEvent> = None;
-                if pending.0.is_empty() {
-                    println!(" No pending action, moving to think");
-                    if player_state.as_ref() == &PlayerState::Dead { return; }
-                    taken_action = Some(ActionEvent(a_think(top, ai_agent.0.into())));
-                } else {
-                    println!(" Pending action found, performing it");
-                    taken_action = Some(ActionEvent(pending.0.pop_front().unwrap()));
-                }
-
-                if let Some(action) = taken_action {
-                    let cost = character.calculate_cost(action.0.get_affiliated_stat());
-                    println!("Action arranged, costs: {:?}", cost);
-                    turn_order.pushback(cost);
-                    actions.send(action);
-                }
-            } else {
-                println!("SOMETHING IS OFF HERE!");
-                turn_order.pushback(100);
+        let Some(strategy) = world.get::<AIAgent>(top).map(|agent| agent.0) else {
+            // only the player and monsters are meant to be in the turn order
+            warn!("{:?} takes turns but has no AI, skipping it", top);
+            let mut turn_order = world.resource_mut::<TurnOrder>();
+            turn_order.pushback(TURN as i32);
+            turn_order.tick();
+            let due = turn_order.take_due();
+            for action in due {
+                world.send_event(ActionEvent(action));
             }
-        }
+            continue;
+        };
+
+        // a plan that can't be followed anymore is dropped by `submit_action`, so next time
+        // around the monster thinks again
+        let planned = world
+            .get_mut::<PendingActions>(top)
+            .and_then(|mut pending| pending.0.pop_front());
+        let action = planned.unwrap_or_else(|| a_think(top, strategy));
+        submit_action(world, action);
     }
-
-    println!("Player time");
 }
 
 pub fn get_player(world: &mut World) -> Option<Entity> {
@@ -141,16 +122,6 @@ pub fn get_player(world: &mut World) -> Option<Entity> {
     Some(p)
 }
 
-pub fn get_mobs(world: &mut World) -> Option<Vec<(Entity, WorldEntity)>> {
-    let mut world_state = SystemState::<Query<(Entity, &WorldEntity), With<Mob>>>::new(world);
-    let mob_query = world_state.get(world);
-    if mob_query.is_empty() {
-        None 
-    } else {
-        Some(mob_query.into_iter().map(|(e, w)| (e, w.clone())).collect::<Vec<_>>())
-    }
-}
-
 pub fn get_the_healer(world: &mut World) -> Option<Entity> {
     let mut world_state = SystemState::<Query<Entity, With<TheHealer>>>::new(world);
     let the_healer_query = world_state.get(world);
@@ -190,3 +161,11 @@ impl Plugin for SvarogAIPlugin {
         bevy.add_systems(Update, ai_agents_act.run_if(in_state(GameStates::Game)));
     }
 }
+
+pub struct SvarogAIDebugPlugin;
+
+impl Plugin for SvarogAIDebugPlugin {
+    fn build(&self, bevy: &mut App) {
+        bevy.add_systems(Update, debug_utility_scores);
+    }
+}
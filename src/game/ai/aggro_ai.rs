@@ -22,11 +22,11 @@ impl AIBehaviour for AggroAIThinking {
 
         let stats = { get_positions_and_health(world, &[entity, player]) };
 
-        let Some((player_pos, player_hp)) = stats.get(&player).cloned().unwrap_or_default() else {
+        let Some((_player_pos, _player_hp)) = stats.get(&player).cloned().unwrap_or_default() else {
             return vec![a_random_walk(entity)];
         };
 
-        let Some((enemy_pos, enemy_hp)) = stats.get(&entity).cloned().unwrap_or_default() else {
+        let Some((_enemy_pos, _enemy_hp)) = stats.get(&entity).cloned().unwrap_or_default() else {
             return vec![a_random_walk(entity)];
         };
 
@@ -36,7 +36,7 @@ impl AIBehaviour for AggroAIThinking {
             a_track(entity, player),
             a_track(entity, player),
             a_track(entity, player),
-            if rng.percent(20u32) { a_wait() } else { a_track(entity, player) },
+            if rng.percent(20u32) { a_wait(entity) } else { a_track(entity, player) },
         ]
     }
 }
@@ -1,9 +1,7 @@
 use crate::game::{
     actions::*,
-    character::CharacterStat,
     feel::Random,
-    inventory::{ItemBuilder, ItemType},
-    sprites::BONES,
+    inventory::{item_from_template, ItemBuilder},
 };
 use bevy::prelude::*;
 
@@ -34,7 +32,7 @@ impl AIBehaviour for AggroCasterAIThinking {
             return vec![a_random_walk(entity)];
         };
 
-        let Some((enemy_pos, mut enemy_hp)) = stats.get(&entity).cloned().unwrap_or_default() else {
+        let Some((enemy_pos, _enemy_hp)) = stats.get(&entity).cloned().unwrap_or_default() else {
             return vec![a_random_walk(entity)];
         };
 
@@ -53,34 +51,15 @@ impl AIBehaviour for AggroCasterAIThinking {
         if distance > 20.0 {
             vec![a_random_walk(entity), a_track(entity, player), a_focus(entity), a_focus(entity)]
         } else {
+            let artifact = item_from_template(world, "curse").map(ItemBuilder::to_item);
             let mut rng = world.get_resource_mut::<Random>().unwrap();
-            let artifact = {
-                let mut builder = ItemBuilder::default()
-                    .with_name("IMAGINARY ITEM")
-                    .with_image(BONES)
-                    .with_type(ItemType::Artifact);
-
-                let stats = [
-                    CharacterStat::STR,
-                    CharacterStat::ARC,
-                    CharacterStat::INT,
-                    CharacterStat::WIS,
-                    CharacterStat::WIL,
-                    CharacterStat::AGI,
-                ];
-                for _ in 0..rng.gen(2..5) {
-                    let stat = rng.from(&stats);
-                    let power = rng.gen(-5..-3);
-                    builder = builder.with_stat(stat, power);
-                }
-
-                builder.to_item()
-            };
 
             let mut actions = vec![];
             let distance = (player_pos.distance_squared(enemy_pos) as f32).sqrt();
             if distance <= 5.0 {
-                actions.push(a_inflict(entity, player, artifact));
+                if let Some(artifact) = artifact {
+                    actions.push(a_inflict(entity, player, artifact));
+                }
             }
 
             for _ in 0..rng.gen(1..3) {
@@ -0,0 +1,218 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+use doryen_fov::{FovAlgorithm, FovRecursiveShadowCasting, MapData};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    actions::*,
+    character::Character,
+    fov::{sight_radius, Sight},
+    grid::{Grid, Passability, WorldData, WorldEntity},
+    health::Health,
+    history::{args, HistoryLog},
+    procgen::PlayerMarker,
+    status::{suffers, StatusEffect, StatusEffects, BLINDED_SIGHT},
+};
+
+use super::items::UsableItems;
+
+// how many times a monster thinks about where the player went before giving up
+const LOST_TRACK_TURNS: u32 = 8;
+
+// how long something glimpsed or heard from afar keeps a monster looking: long enough to
+// walk over to a yell from across the room
+const SUSPICIOUS_TURNS: u32 = 14;
+
+// what a monster knows about the player, which is only ever what it has seen itself
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Awareness {
+    #[default]
+    Unaware,
+    // glimpsed something at the edge of its sight, and is going to have a look
+    Suspicious { at: IVec2, turns: u32 },
+    Hunting { last_seen: IVec2 },
+    // doesn't see the player anymore, but remembers where they were and follows
+    LostTrack { last_seen: IVec2, turns: u32 },
+}
+
+impl Awareness {
+    // `seen` is where the player is, if the monster can see them, and `close` is whether
+    // they're in the nearer half of its sight
+    pub fn update(self, seen: Option<IVec2>, close: bool, position: IVec2) -> Self {
+        match (self, seen) {
+            (Awareness::Unaware, Some(at)) if !close => Awareness::Suspicious { at, turns: 0 },
+            (_, Some(at)) => Awareness::Hunting { last_seen: at },
+            (Awareness::Hunting { last_seen }, None) => Awareness::LostTrack {
+                last_seen,
+                turns: 0,
+            },
+            (Awareness::LostTrack { last_seen, turns }, None)
+                if turns < LOST_TRACK_TURNS && position != last_seen =>
+            {
+                Awareness::LostTrack {
+                    last_seen,
+                    turns: turns + 1,
+                }
+            }
+            (Awareness::Suspicious { at, turns }, None)
+                if turns < SUSPICIOUS_TURNS && position != at =>
+            {
+                Awareness::Suspicious {
+                    at,
+                    turns: turns + 1,
+                }
+            }
+            _ => Awareness::Unaware,
+        }
+    }
+
+    // a noise doesn't tell a monster that already sees the player anything new, but anyone
+    // else goes to have a look at where it came from
+    pub fn hear(self, at: IVec2) -> Self {
+        match self {
+            Awareness::Hunting { .. } => self,
+            Awareness::LostTrack { .. } => Awareness::LostTrack {
+                last_seen: at,
+                turns: 0,
+            },
+            _ => Awareness::Suspicious { at, turns: 0 },
+        }
+    }
+
+    // being told where the player is by an ally is as good as having lost sight of them
+    pub fn told(self, at: IVec2) -> Self {
+        match self {
+            Awareness::Hunting { .. } => self,
+            _ => Awareness::LostTrack {
+                last_seen: at,
+                turns: 0,
+            },
+        }
+    }
+
+    pub fn is_hunting(&self) -> bool {
+        matches!(self, Awareness::Hunting { .. })
+    }
+
+    // whether the monster knows the player is around, rather than just having a hunch
+    pub fn has_noticed(&self) -> bool {
+        matches!(self, Awareness::Hunting { .. } | Awareness::LostTrack { .. })
+    }
+}
+
+// how many tiles closer than their sight a monster has to be to pick out the player.
+// the quick and the clever keep to the shadows, and the forest hides anyone.
+pub fn stealth(character: &Character, in_cover: bool) -> usize {
+    let sneaking = ((character.agility + character.intelligence) / 4).clamp(0, 3) as usize;
+    if in_cover {
+        sneaking + 2
+    } else {
+        sneaking
+    }
+}
+
+// whether `to` is in the field of view of someone standing at `from` who sees `radius` tiles.
+// works on a copy of just the part of the level within `radius`, so the player's own FOV is
+// left alone and monsters don't pay for the whole map every time they look.
+pub fn can_see(grid: &Grid, world_data: &WorldData, from: IVec2, to: IVec2, radius: usize) -> bool {
+    let (fx, fy) = grid.norm(from);
+    let (tx, ty) = grid.norm(to);
+    if fx.abs_diff(tx) > radius || fy.abs_diff(ty) > radius {
+        return false;
+    }
+
+    let (width, height) = ((grid.size.x + 2) as usize, (grid.size.y + 2) as usize);
+    let (left, top) = (fx.saturating_sub(radius), fy.saturating_sub(radius));
+    let (right, bottom) = ((fx + radius).min(width - 1), (fy + radius).min(height - 1));
+
+    let mut map = MapData::new(right - left + 1, bottom - top + 1);
+    for x in left..=right {
+        for y in top..=bottom {
+            map.set_transparent(x - left, y - top, world_data.data.is_transparent(x, y));
+        }
+    }
+
+    FovRecursiveShadowCasting::default().compute_fov(&mut map, fx - left, fy - top, radius, true);
+    map.is_in_fov(tx - left, ty - top)
+}
+
+// looks around for the player and updates what the monster knows. monsters without an
+// `Awareness` always know where the player is, as they did before anyone could hide.
+pub fn perceive(world: &mut World, entity: Entity) -> Option<Awareness> {
+    let mut world_state = SystemState::<(
+        Res<Grid>,
+        Res<WorldData>,
+        Query<(
+            &WorldEntity,
+            &Sight,
+            &Character,
+            &mut Awareness,
+            Option<&Health>,
+            Option<&StatusEffects>,
+        )>,
+        Query<(&WorldEntity, &Character), With<PlayerMarker>>,
+        Query<&Passability>,
+        ResMut<HistoryLog>,
+    )>::new(world);
+    let (grid, world_data, mut monsters, player, tiles, mut log) = world_state.get_mut(world);
+
+    let Ok((world_entity, sight, character, mut awareness, health, effects)) =
+        monsters.get_mut(entity)
+    else {
+        return None;
+    };
+
+    let Ok((player, player_character)) = player.get_single() else {
+        return Some(*awareness);
+    };
+
+    let in_cover = grid
+        .entities
+        .get(&player.position)
+        .and_then(|tile| tiles.get(*tile).ok())
+        .map(|passability| *passability == Passability::SightBlocking)
+        .unwrap_or_default();
+
+    // nobody sneaks up on someone right next to them
+    let radius = if suffers(effects, health, StatusEffect::Blinded) {
+        BLINDED_SIGHT
+    } else {
+        sight_radius(sight, character)
+    };
+    let radius = radius.saturating_sub(stealth(player_character, in_cover)).max(1);
+    let seen = can_see(&grid, &world_data, world_entity.position, player.position, radius)
+        .then_some(player.position);
+    let distance = (world_entity.position - player.position).abs();
+    let close = distance.x.max(distance.y) as usize <= radius.div_ceil(2);
+
+    let updated = awareness.update(seen, close, world_entity.position);
+    if updated.is_hunting() && !awareness.has_noticed() {
+        log.say("awareness.noticed", args().subject(world_entity));
+    }
+
+    *awareness = updated;
+    Some(updated)
+}
+
+// what a monster that doesn't see the player does instead of following its strategy: looks
+// for them if it has a hunch, and otherwise sees to its gear
+pub fn think_without_sight(
+    world: &mut World,
+    entity: Entity,
+    awareness: Awareness,
+) -> Vec<AbstractAction> {
+    match awareness {
+        Awareness::Suspicious { at, .. } | Awareness::LostTrack { last_seen: at, .. } => {
+            vec![a_investigate(entity, at)]
+        }
+        _ => match UsableItems::of(world, entity) {
+            UsableItems {
+                equip: Some(item), ..
+            } => vec![a_equip(entity, item)],
+            UsableItems {
+                consume: Some(item),
+                ..
+            } => vec![a_consume(entity, item)],
+            _ => vec![a_scavenge(entity)],
+        },
+    }
+}
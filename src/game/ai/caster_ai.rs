@@ -1,9 +1,7 @@
 use crate::game::{
     actions::*,
-    character::CharacterStat,
     feel::Random,
-    inventory::{ItemBuilder, ItemType},
-    sprites::BONES,
+    inventory::{item_from_template, ItemBuilder},
 };
 use bevy::prelude::*;
 
@@ -34,7 +32,7 @@ impl AIBehaviour for CasterThinking {
             return vec![a_random_walk(entity)];
         };
 
-        let Some((enemy_pos, mut enemy_hp)) = stats.get_mut(&entity).cloned().unwrap_or_default() else {
+        let Some((enemy_pos, enemy_hp)) = stats.get_mut(&entity).cloned().unwrap_or_default() else {
             return vec![a_random_walk(entity)];
         };
 
@@ -56,50 +54,24 @@ impl AIBehaviour for CasterThinking {
         if distance > 10.0 {
             vec![a_random_walk(entity), a_track(entity, player), a_focus(entity)]
         } else {
-            let mut rng = world.get_resource_mut::<Random>().unwrap();
+            let casts = world.get_resource_mut::<Random>().unwrap().gen(1..3);
 
             let mut actions = vec![];
 
-            for _ in 0..rng.gen(1..3) {
-                let artifact = {
-                    let mut builder = ItemBuilder::default()
-                        .with_name("IMAGINARY ITEM")
-                        .with_image(BONES)
-                        .with_type(ItemType::Artifact);
-    
-                    let stats = [
-                        CharacterStat::STR,
-                        CharacterStat::ARC,
-                        CharacterStat::INT,
-                        CharacterStat::WIS,
-                        CharacterStat::WIL,
-                        CharacterStat::AGI,
-                    ];
-                    for _ in 0..rng.gen(2..5) {
-                        let stat = rng.from(&stats);
-                        let power = rng.gen(-3..-1);
-                        builder = builder.with_stat(stat, power);
-                    }
-    
-                    builder.to_item()
-                };
+            for _ in 0..casts {
+                let artifact = item_from_template(world, "lesser curse").map(ItemBuilder::to_item);
+                let mut rng = world.get_resource_mut::<Random>().unwrap();
                 for _ in 0..rng.gen(2..4) {
-                    if rng.coin() {
-                        actions.push(a_track(entity, player));    
-                    } else {
-                        actions.push(a_random_walk(entity));
-                    }
+                    actions.push(a_kite(entity, player));
                 }
                 let distance = (player_pos.distance_squared(enemy_pos) as f32).sqrt();
                 if distance <= 7.0 {
-                    actions.push(a_inflict(entity, player, artifact));
+                    if let Some(artifact) = artifact {
+                        actions.push(a_inflict(entity, player, artifact));
+                    }
                 }
                 for _ in 0..rng.gen(2..4) {
-                    if rng.coin() {
-                        actions.push(a_track(entity, player));    
-                    } else {
-                        actions.push(a_random_walk(entity));
-                    }
+                    actions.push(a_kite(entity, player));
                 }
             }
             actions        
@@ -0,0 +1,95 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{grid::WorldEntity, mobs::Mob};
+
+use super::awareness::Awareness;
+
+// how far a monster that sees the player can let the rest of its faction know
+const CALLING_RANGE: i32 = 8;
+
+// who a monster sides with. everyone's against the player, and every faction is against
+// every other one.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Faction {
+    // the Healer and those who follow them
+    Cult,
+    // bats and the like, which go for anything
+    Beasts,
+    Goblins,
+}
+
+impl Faction {
+    // creatures without a faction (like the player) are left to `WorldEntity::is_player`
+    pub fn hostile(a: Option<&Faction>, b: Option<&Faction>) -> bool {
+        matches!((a, b), (Some(a), Some(b)) if a != b)
+    }
+}
+
+fn chebyshev(a: IVec2, b: IVec2) -> i32 {
+    let d = (a - b).abs();
+    d.x.max(d.y)
+}
+
+// the mobs of the same faction as `entity`, nearest first
+pub fn get_allied_mobs(world: &mut World, entity: Entity) -> Vec<(Entity, WorldEntity)> {
+    let mut world_state =
+        SystemState::<Query<(Entity, &WorldEntity, Option<&Faction>, Option<&Mob>)>>::new(world);
+    let creatures = world_state.get(world);
+
+    let Ok((_, me, Some(faction), _)) = creatures.get(entity) else {
+        return vec![];
+    };
+
+    let mut allies = creatures
+        .iter()
+        .filter(|(other, _, other_faction, mob)| {
+            *other != entity && *other_faction == Some(faction) && mob.is_some()
+        })
+        .map(|(other, world_entity, ..)| (other, world_entity.clone()))
+        .collect::<Vec<_>>();
+
+    allies.sort_by_key(|(_, world_entity)| chebyshev(world_entity.position, me.position));
+    allies
+}
+
+// which way the nearest monster of another faction is, if one is right next to `entity`
+pub fn adjacent_rival(world: &mut World, entity: Entity) -> Option<IVec2> {
+    let mut world_state = SystemState::<Query<(Entity, &WorldEntity, &Faction)>>::new(world);
+    let creatures = world_state.get(world);
+
+    let (_, me, faction) = creatures.get(entity).ok()?;
+    creatures
+        .iter()
+        .find(|(other, world_entity, other_faction)| {
+            *other != entity
+                && Faction::hostile(Some(faction), Some(*other_faction))
+                && chebyshev(world_entity.position, me.position) == 1
+        })
+        .map(|(_, world_entity, _)| world_entity.position - me.position)
+}
+
+// a monster that sees the player lets its faction nearby know where they are
+pub fn share_awareness(world: &mut World, entity: Entity) {
+    let mut world_state =
+        SystemState::<Query<(Entity, &WorldEntity, &Faction, &mut Awareness)>>::new(world);
+    let mut creatures = world_state.get_mut(world);
+
+    let Ok((_, me, faction, awareness)) = creatures.get(entity) else {
+        return;
+    };
+
+    let Awareness::Hunting { last_seen } = *awareness else {
+        return;
+    };
+
+    let (position, faction) = (me.position, *faction);
+    for (other, world_entity, other_faction, mut awareness) in &mut creatures {
+        if other != entity
+            && *other_faction == faction
+            && chebyshev(world_entity.position, position) <= CALLING_RANGE
+        {
+            *awareness = awareness.told(last_seen);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use crate::game::{
+    actions::equip_action::MAX_EQUIPPED,
+    inventory::{CarriedItems, EquippedItems, Item, ItemType},
+};
+
+// what an item is worth to a monster: the sum of what it does to stats
+pub fn worth(item: &Item) -> i32 {
+    item.equip_stat_changes.iter().map(|(_, val)| *val).sum()
+}
+
+// monsters don't read, so scrolls (and anything unknown) are left where they lie
+pub fn worth_taking(item: &Item) -> bool {
+    matches!(
+        item.item_type,
+        ItemType::Weapon | ItemType::Armor | ItemType::Artifact
+    )
+}
+
+// the best thing a monster could do with what it carries, if anything
+#[derive(Default, Debug)]
+pub struct UsableItems {
+    // a weapon or armor that would make it stronger, if there's still room for it
+    pub equip: Option<Entity>,
+    // bones that would make it grow
+    pub consume: Option<Entity>,
+    // bones that would hurt whoever they're thrown at
+    pub throw: Option<Entity>,
+}
+
+impl UsableItems {
+    pub fn of(world: &mut World, entity: Entity) -> Self {
+        let mut world_state =
+            SystemState::<(Query<(&CarriedItems, &EquippedItems)>, Query<&Item>)>::new(world);
+        let (inventories, items) = world_state.get(world);
+
+        let mut usable = UsableItems::default();
+        let Ok((carried, equipped)) = inventories.get(entity) else {
+            return usable;
+        };
+
+        let mut best = (0, 0, 0);
+        for item_entity in carried.0.iter().filter(|i| !equipped.0.contains(i)) {
+            let Ok(item) = items.get(*item_entity) else {
+                continue;
+            };
+
+            let value = worth(item);
+            match item.item_type {
+                ItemType::Weapon | ItemType::Armor
+                    if equipped.0.len() < MAX_EQUIPPED && value > best.0 =>
+                {
+                    best.0 = value;
+                    usable.equip = Some(*item_entity);
+                }
+                ItemType::Artifact if value > best.1 => {
+                    best.1 = value;
+                    usable.consume = Some(*item_entity);
+                }
+                ItemType::Artifact if value < best.2 => {
+                    best.2 = value;
+                    usable.throw = Some(*item_entity);
+                }
+                _ => {}
+            }
+        }
+
+        usable
+    }
+}
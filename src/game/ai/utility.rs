@@ -0,0 +1,376 @@
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_mod_imgui::ImguiContext;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    actions::{throw_action::throw_range, *},
+    character::Character,
+    fov::{sight_radius, Sight},
+    grid::{Grid, WorldData, WorldEntity},
+    health::Health,
+    inventory::{item_from_template, CarriedMarker, Item},
+    magic::Focus,
+    mobs::MonsterCatalog,
+    spells::blink_destination,
+    DebugFlag,
+};
+
+use super::{
+    awareness::can_see,
+    factions::Faction,
+    get_player,
+    items::{worth_taking, UsableItems},
+    AIBehaviour, AbstractAIBehaviour,
+};
+
+// how far away the target can be before `Distance` stops growing
+const FAR_AWAY: f32 = 10.0;
+
+// how much focus counts as plenty
+const FULL_FOCUS: f32 = 5.0;
+
+// how close others of the same faction have to be to count as nearby, and how many of them
+// is a crowd
+const ALLIES_RANGE: i32 = 5;
+const CROWD: f32 = 3.0;
+
+// something a monster takes into account when it thinks, always between 0 and 1
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Consideration {
+    // how far the player is, up to `FAR_AWAY`
+    Distance,
+    OwnHealth,
+    TargetHealth,
+    // 1 if the monster can see the player, 0 otherwise
+    LineOfSight,
+    Focus,
+    AlliesNearby,
+    // how many of the monster's faction are already right next to the player, 1 from 2 on
+    AlliesEngaged,
+    // how close the nearest thing worth taking is: 1 underfoot, down to 0 from `FAR_AWAY` on
+    ItemsNearby,
+    // 1 if the monster carries something it could put on, eat or throw (at a player within
+    // range), 0 otherwise
+    CanEquip,
+    CanConsume,
+    CanThrow,
+}
+
+// how a consideration turns into a score
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+pub enum Curve {
+    Rising,
+    Falling,
+    // 1 at or above the value, 0 below it
+    Above(f32),
+    // 1 at or below the value, 0 above it
+    Below(f32),
+}
+
+impl Curve {
+    pub fn apply(&self, value: f32) -> f32 {
+        match *self {
+            Curve::Rising => value,
+            Curve::Falling => 1.0 - value,
+            Curve::Above(threshold) => (value >= threshold) as i32 as f32,
+            Curve::Below(threshold) => (value <= threshold) as i32 as f32,
+        }
+    }
+}
+
+// something a monster can decide to do, and the actions that it takes
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum Plan {
+    Melee,
+    // like `Melee`, but comes at the player from a side nobody's on yet
+    Surround,
+    Kite,
+    // jumps as far away from the player as it can
+    Blink,
+    // curses the player with an item rolled from the given template, and backs off
+    Curse(String),
+    Heal,
+    Flee,
+    Focus,
+    Wander,
+    // goes for whatever's worth taking nearby, and picks it up
+    Scavenge,
+    Equip,
+    Consume,
+    // throws harmful bones at the player
+    Throw,
+}
+
+impl Plan {
+    pub fn actions(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        target: Entity,
+    ) -> Vec<AbstractAction> {
+        match self {
+            Plan::Melee => vec![
+                a_track(entity, target),
+                a_track(entity, target),
+                a_track(entity, target),
+            ],
+            Plan::Surround => vec![
+                a_surround(entity, target),
+                a_surround(entity, target),
+                a_surround(entity, target),
+            ],
+            Plan::Kite => vec![a_kite(entity, target), a_kite(entity, target)],
+            Plan::Blink => {
+                let away_from = world.get::<WorldEntity>(target).map(|t| t.position);
+                match blink_destination(world, entity, away_from) {
+                    Some(wher) => vec![a_teleport(entity, wher)],
+                    None => vec![a_kite(entity, target)],
+                }
+            }
+            Plan::Curse(template) => match item_from_template(world, template) {
+                Some(artifact) => vec![
+                    a_inflict(entity, target, artifact.to_item()),
+                    a_kite(entity, target),
+                    a_kite(entity, target),
+                ],
+                None => vec![a_random_walk(entity)],
+            },
+            Plan::Heal => vec![a_heal(entity)],
+            Plan::Flee => vec![a_flee(entity, target), a_flee(entity, target)],
+            Plan::Focus => vec![a_focus(entity)],
+            Plan::Wander => vec![a_random_walk(entity)],
+            Plan::Scavenge => vec![a_scavenge(entity), a_scavenge(entity)],
+            Plan::Equip | Plan::Consume | Plan::Throw => {
+                let usable = UsableItems::of(world, entity);
+                let at = world.get::<WorldEntity>(target).map(|t| t.position);
+                match (self, usable, at) {
+                    (Plan::Equip, UsableItems { equip: Some(item), .. }, _) => {
+                        vec![a_equip(entity, item)]
+                    }
+                    (Plan::Consume, UsableItems { consume: Some(item), .. }, _) => {
+                        vec![a_consume(entity, item)]
+                    }
+                    (Plan::Throw, UsableItems { throw: Some(item), .. }, Some(at)) => {
+                        vec![a_throw(entity, item, at)]
+                    }
+                    _ => vec![a_random_walk(entity)],
+                }
+            }
+        }
+    }
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+// a plan is worth its weight times every one of its scored considerations, so any one of them
+// being 0 rules it out
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PlanDef {
+    pub plan: Plan,
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    #[serde(default)]
+    pub considerations: Vec<(Consideration, Curve)>,
+}
+
+impl PlanDef {
+    pub fn score(&self, situation: &Situation) -> f32 {
+        self.considerations
+            .iter()
+            .fold(self.weight, |score, (consideration, curve)| {
+                score * curve.apply(situation.get(*consideration))
+            })
+    }
+}
+
+// which of the monster catalog's utility profiles a monster thinks with
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct UtilityProfile(pub String);
+
+// how every plan scored the last time the monster thought, for the debug window
+#[derive(Component, Default, Debug)]
+pub struct UtilityScores(pub Vec<(Plan, f32)>);
+
+// everything a monster considers, already scaled to be between 0 and 1
+#[derive(Default, Debug)]
+pub struct Situation {
+    pub distance: f32,
+    pub own_health: f32,
+    pub target_health: f32,
+    pub line_of_sight: f32,
+    pub focus: f32,
+    pub allies_nearby: f32,
+    pub allies_engaged: f32,
+    pub items_nearby: f32,
+    pub can_equip: f32,
+    pub can_consume: f32,
+    pub can_throw: f32,
+}
+
+impl Situation {
+    pub fn get(&self, consideration: Consideration) -> f32 {
+        match consideration {
+            Consideration::Distance => self.distance,
+            Consideration::OwnHealth => self.own_health,
+            Consideration::TargetHealth => self.target_health,
+            Consideration::LineOfSight => self.line_of_sight,
+            Consideration::Focus => self.focus,
+            Consideration::AlliesNearby => self.allies_nearby,
+            Consideration::AlliesEngaged => self.allies_engaged,
+            Consideration::ItemsNearby => self.items_nearby,
+            Consideration::CanEquip => self.can_equip,
+            Consideration::CanConsume => self.can_consume,
+            Consideration::CanThrow => self.can_throw,
+        }
+    }
+
+    // looks around from `entity`'s point of view, returning its plans along with what it sees
+    pub fn assess(
+        world: &mut World,
+        entity: Entity,
+        target: Entity,
+    ) -> Option<(Self, Vec<PlanDef>)> {
+        let usable = UsableItems::of(world, entity);
+        let range = world.get::<Character>(entity).map(throw_range).unwrap_or_default();
+        let mut world_state = SystemState::<(
+            Res<Grid>,
+            Res<WorldData>,
+            Res<MonsterCatalog>,
+            Query<(
+                &WorldEntity,
+                &Health,
+                Option<&Focus>,
+                Option<(&Sight, &Character)>,
+                Option<&UtilityProfile>,
+            )>,
+            Query<(Entity, &WorldEntity, &Faction)>,
+            Query<(&WorldEntity, &Item), Without<CarriedMarker>>,
+        )>::new(world);
+        let (grid, world_data, catalog, creatures, factions, items) = world_state.get(world);
+
+        let (me, my_health, focus, sight, profile) = creatures.get(entity).ok()?;
+        let (them, their_health, ..) = creatures.get(target).ok()?;
+        let plans = catalog.utility.get(&profile?.0)?.clone();
+
+        let delta = (me.position - them.position).abs();
+        let line_of_sight = match sight {
+            Some((sight, character)) => {
+                let radius = sight_radius(sight, character);
+                can_see(&grid, &world_data, me.position, them.position, radius)
+            }
+            None => true,
+        };
+
+        let faction = factions.get(entity).ok().map(|(_, _, faction)| *faction);
+        let allies = factions
+            .iter()
+            .filter(|(ally, _, ally_faction)| *ally != entity && Some(**ally_faction) == faction)
+            .map(|(_, world_entity, _)| world_entity.position)
+            .collect::<Vec<_>>();
+        let within = |from: IVec2, range: i32| {
+            allies
+                .iter()
+                .filter(|ally| {
+                    let d = (**ally - from).abs();
+                    d.x.max(d.y) <= range
+                })
+                .count() as f32
+        };
+
+        let nearest_item = items
+            .iter()
+            .filter(|(_, item)| worth_taking(item))
+            .map(|(world_entity, _)| {
+                let d = (world_entity.position - me.position).abs();
+                d.x.max(d.y)
+            })
+            .min();
+
+        let situation = Situation {
+            distance: (delta.x.max(delta.y) as f32 / FAR_AWAY).min(1.0),
+            own_health: my_health.hitpoints.len() as f32 / my_health.size.max(1) as f32,
+            target_health: their_health.hitpoints.len() as f32 / their_health.size.max(1) as f32,
+            line_of_sight: line_of_sight as i32 as f32,
+            focus: (focus.map(|f| f.0).unwrap_or_default() as f32 / FULL_FOCUS).min(1.0),
+            allies_nearby: (within(me.position, ALLIES_RANGE) / CROWD).min(1.0),
+            allies_engaged: (within(them.position, 1) / 2.0).min(1.0),
+            items_nearby: nearest_item
+                .map(|d| 1.0 - (d as f32 / FAR_AWAY).min(1.0))
+                .unwrap_or_default(),
+            can_equip: usable.equip.is_some() as i32 as f32,
+            can_consume: usable.consume.is_some() as i32 as f32,
+            // throws that fall short get rejected, so there's no planning them
+            can_throw: (usable.throw.is_some() && delta.x.max(delta.y) <= range) as i32 as f32,
+        };
+
+        Some((situation, plans))
+    }
+}
+
+#[derive(Debug)]
+pub struct UtilityThinking;
+
+pub fn ai_utility() -> AbstractAIBehaviour {
+    Box::new(UtilityThinking)
+}
+
+impl AIBehaviour for UtilityThinking {
+    fn do_thinking(&self, entity: Entity, world: &mut World) -> Vec<AbstractAction> {
+        let Some(player) = get_player(world) else {
+            return vec![a_random_walk(entity)];
+        };
+
+        let Some((situation, plans)) = Situation::assess(world, entity, player) else {
+            return vec![a_random_walk(entity)];
+        };
+
+        let scores = plans
+            .iter()
+            .map(|plan| (plan.plan.clone(), plan.score(&situation)))
+            .collect::<Vec<_>>();
+
+        // the first of the best scoring plans, as long as it's worth doing at all
+        let mut best: Option<&(Plan, f32)> = None;
+        for candidate in &scores {
+            if candidate.1 > best.map(|b| b.1).unwrap_or(0.0) {
+                best = Some(candidate);
+            }
+        }
+
+        let actions = match best {
+            Some((plan, _)) => plan.actions(world, entity, player),
+            None => vec![a_random_walk(entity)],
+        };
+
+        world.entity_mut(entity).insert(UtilityScores(scores));
+        actions
+    }
+}
+
+pub fn debug_utility_scores(
+    mut context: NonSendMut<ImguiContext>,
+    thinkers: Query<(&WorldEntity, &UtilityScores)>,
+    debug: Res<DebugFlag>,
+) {
+    if !debug.0 {
+        return;
+    }
+
+    let ui = context.ui();
+    let window = ui.window("Utility");
+
+    window
+        .size([200.0, 300.0], imgui::Condition::FirstUseEver)
+        .save_settings(true)
+        .build(|| {
+            for (entity, scores) in &thinkers {
+                ui.text(&entity.name);
+                for (plan, score) in &scores.0 {
+                    ui.text(format!("  {:?}: {:.2}", plan, score));
+                }
+                ui.separator();
+            }
+        });
+}
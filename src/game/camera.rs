@@ -11,7 +11,9 @@ use bevy_mouse_tracking_plugin::{
 use bevy_trauma_shake::{Shake, ShakeSettings};
 
 use super::{
+    actions::handle_gameplay_action,
     grid::{Grid, WorldEntity},
+    outcomes::ActionOutcome,
     procgen::{PlayerMarker, ProcGenEvent},
     DebugFlag, GameStates,
 };
@@ -225,6 +227,23 @@ fn setup_cameras(mut commands: Commands, mut procgen_events: EventWriter<ProcGen
     procgen_events.send(ProcGenEvent::RestartWorld);
 }
 
+// the harder the player gets hit, the more the screen shakes
+fn shake_on_damage(
+    mut outcomes: EventReader<ActionOutcome>,
+    player: Query<(), With<PlayerMarker>>,
+    mut shake: Query<&mut Shake>,
+) {
+    for outcome in outcomes.read() {
+        if let ActionOutcome::Damaged { target, amount, .. } = *outcome {
+            if player.contains(target) {
+                if let Ok(mut shake) = shake.get_single_mut() {
+                    shake.add_trauma((amount as f32 + 1.0).min(4.0) * 0.01);
+                }
+            }
+        }
+    }
+}
+
 pub struct SvarogCameraPlugin;
 impl Plugin for SvarogCameraPlugin {
     fn build(&self, bevy: &mut bevy::prelude::App) {
@@ -236,6 +255,12 @@ impl Plugin for SvarogCameraPlugin {
         })
         .add_systems(PostUpdate, track_camera.run_if(in_state(GameStates::Game)))
         .add_systems(OnEnter(GameStates::Game), setup_cameras)
+        .add_systems(
+            Update,
+            shake_on_damage
+                .after(handle_gameplay_action)
+                .run_if(on_event::<ActionOutcome>()),
+        )
         .add_systems(PostUpdate, debug_camera);
     }
 }
@@ -1,12 +1,13 @@
 use bevy::prelude::*;
 use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::ops::{Index, IndexMut};
 
 use super::feel::Random;
 use super::magic::Magic;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum CharacterStat {
     STR,
     ARC,
@@ -16,7 +17,7 @@ pub enum CharacterStat {
     AGI,
 }
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Character {
     pub strength: i32,
     pub arcana: i32,
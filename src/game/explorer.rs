@@ -0,0 +1,243 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::{
+    actions::{handle_gameplay_action, ActionEvent},
+    ai::{ai_agents_act, PendingActions},
+    history::{args, HistoryLog},
+    inventory::CarriedItems,
+    player::{character_controls, PlayerState},
+    procgen::{PlayerMarker, ProcGenEvent},
+    replay::RecordedAction,
+    save::SaveGame,
+    turns::TurnOrder,
+    GameStates,
+};
+
+pub const EXPLORER_FLAG: &str = "--explorer";
+
+// how many turns back the player can go
+const REWIND_LIMIT: usize = 100;
+
+pub fn explorer_mode_requested() -> bool {
+    std::env::args().any(|arg| arg == EXPLORER_FLAG)
+}
+
+// only exists in explorer mode: the world as it was at the start of each of the player's turns,
+// oldest first
+#[derive(Resource, Default)]
+pub struct ExplorerMode {
+    pub snapshots: VecDeque<SaveGame>,
+    // whether the last snapshot is of the turn the player is on right now
+    pub taken: bool,
+    pub rewinds: u32,
+}
+
+pub fn snapshot_player_turn(world: &mut World) {
+    // monster actions still waiting to be handled aren't in a snapshot, so rewinding would lose them
+    if world.resource::<ExplorerMode>().taken
+        || *world.resource::<PlayerState>() != PlayerState::Idle
+        || !world.resource::<Events<ActionEvent>>().is_empty()
+    {
+        return;
+    }
+
+    let mut query = world.query_filtered::<(Entity, &PendingActions), With<PlayerMarker>>();
+    let Ok((player, pending)) = query.get_single(world) else {
+        return;
+    };
+
+    if world.resource::<TurnOrder>().peek() != Some(player) || !pending.0.is_empty() {
+        return;
+    }
+
+    let snapshot = SaveGame::capture(world);
+    let mut explorer = world.resource_mut::<ExplorerMode>();
+    explorer.snapshots.push_back(snapshot);
+    if explorer.snapshots.len() > REWIND_LIMIT {
+        explorer.snapshots.pop_front();
+    }
+    explorer.taken = true;
+}
+
+// anything the player chose to do means the next turn needs a snapshot of its own
+pub fn mark_player_actions(
+    mut actions: EventReader<ActionEvent>,
+    mut explorer: ResMut<ExplorerMode>,
+    player_query: Query<(Entity, &CarriedItems), With<PlayerMarker>>,
+) {
+    let Ok((player, carried)) = player_query.get_single() else {
+        return;
+    };
+
+    for action in actions.read() {
+        if RecordedAction::from_kind(action.0.kind(), player, &carried.0).is_some() {
+            explorer.taken = false;
+        }
+    }
+}
+
+fn rewind(world: &mut World) {
+    if !world.resource::<Input<KeyCode>>().just_pressed(KeyCode::U)
+        || !matches!(
+            *world.resource::<PlayerState>(),
+            PlayerState::Idle | PlayerState::Dead
+        )
+    {
+        return;
+    }
+
+    rewind_turn(world);
+}
+
+// goes back to the start of the player's previous turn, random stream and all. returns false
+// if there's nowhere to go back to.
+pub fn rewind_turn(world: &mut World) -> bool {
+    let snapshot = {
+        let mut explorer = world.resource_mut::<ExplorerMode>();
+        // the turn we're on is the one being undone, so its own snapshot goes first
+        if explorer.taken {
+            if explorer.snapshots.len() < 2 {
+                return false;
+            }
+            explorer.snapshots.pop_back();
+        }
+
+        let Some(snapshot) = explorer.snapshots.pop_back() else {
+            return false;
+        };
+
+        explorer.taken = false;
+        explorer.rewinds += 1;
+        snapshot
+    };
+
+    snapshot.restore(world);
+    world.resource_mut::<HistoryLog>().say("explorer.rewound", args());
+    true
+}
+
+fn forget_snapshots(
+    mut procgen_events: EventReader<ProcGenEvent>,
+    mut explorer: ResMut<ExplorerMode>,
+) {
+    for procgen in procgen_events.read() {
+        if *procgen == ProcGenEvent::RestartWorld {
+            *explorer = ExplorerMode::default();
+        }
+    }
+}
+
+// `--explorer` is a practice mode: U rewinds to the start of the player's previous turn.
+// normal runs don't have any of this.
+pub struct SvarogExplorerPlugin;
+
+impl Plugin for SvarogExplorerPlugin {
+    fn build(&self, bevy: &mut App) {
+        if !explorer_mode_requested() {
+            return;
+        }
+
+        bevy.init_resource::<ExplorerMode>()
+            .add_systems(
+                Update,
+                (rewind, snapshot_player_turn)
+                    .chain()
+                    .before(character_controls)
+                    .run_if(in_state(GameStates::Game)),
+            )
+            .add_systems(
+                Update,
+                mark_player_actions
+                    .after(character_controls)
+                    .before(ai_agents_act)
+                    .before(handle_gameplay_action)
+                    .run_if(on_event::<ActionEvent>()),
+            )
+            .add_systems(Update, forget_snapshots.run_if(on_event::<ProcGenEvent>()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::game::{
+        actions::{
+            a_move, a_wait,
+            tests::{clumsy, save_world, spawn_fighter, strong},
+        },
+        feel::Random,
+        grid::WorldEntity,
+    };
+
+    #[test]
+    fn explorers_rewind_to_the_start_of_their_last_turn() {
+        let mut world = save_world();
+        world.init_resource::<ExplorerMode>();
+        *world.resource_mut::<PlayerState>() = PlayerState::Idle;
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+        world.entity_mut(player).insert(PendingActions::default());
+        world.resource_mut::<TurnOrder>().insert(player, 0);
+
+        snapshot_player_turn(&mut world);
+        let roll = world.resource_mut::<Random>().next_seed();
+
+        world.send_event(ActionEvent(a_move(player, IVec2::Y)));
+        world.run_system_once(mark_player_actions);
+        handle_gameplay_action(&mut world);
+        assert_eq!(world.get::<WorldEntity>(player).unwrap().position, IVec2::Y);
+
+        // monsters waiting around isn't the player doing anything
+        let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+        snapshot_player_turn(&mut world);
+        world.send_event(ActionEvent(a_wait(goblin)));
+        world.run_system_once(mark_player_actions);
+        assert!(world.resource::<ExplorerMode>().taken);
+        assert_eq!(world.resource::<ExplorerMode>().snapshots.len(), 2);
+
+        assert!(rewind_turn(&mut world));
+        let mut players = world.query_filtered::<&WorldEntity, With<PlayerMarker>>();
+        assert_eq!(players.single(&world).position, IVec2::ZERO);
+        assert_eq!(world.resource_mut::<Random>().next_seed(), roll);
+        assert!(!rewind_turn(&mut world));
+    }
+
+    #[test]
+    fn rewinding_keeps_what_monsters_did_before_the_turn() {
+        let mut world = save_world();
+        world.init_resource::<ExplorerMode>();
+        *world.resource_mut::<PlayerState>() = PlayerState::Idle;
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+        let goblin = spawn_fighter(&mut world, "Goblin", IVec2::new(3, 0), clumsy(), 3);
+        world.entity_mut(player).insert(PendingActions::default());
+        world.resource_mut::<TurnOrder>().insert(player, 0);
+
+        let player_moves = |world: &mut World| {
+            world.send_event(ActionEvent(a_move(player, IVec2::Y)));
+            world.run_system_once(mark_player_actions);
+            handle_gameplay_action(world);
+        };
+
+        snapshot_player_turn(&mut world);
+        player_moves(&mut world);
+
+        // the goblin's step is still on its way when the player's next turn comes up
+        world.send_event(ActionEvent(a_move(goblin, IVec2::NEG_X)));
+        snapshot_player_turn(&mut world);
+        assert!(!world.resource::<ExplorerMode>().taken);
+        handle_gameplay_action(&mut world);
+        snapshot_player_turn(&mut world);
+        assert!(world.resource::<ExplorerMode>().taken);
+
+        player_moves(&mut world);
+        assert!(rewind_turn(&mut world));
+
+        let mut monsters = world.query_filtered::<&WorldEntity, Without<PlayerMarker>>();
+        assert_eq!(monsters.single(&world).position, IVec2::new(2, 0));
+        let mut players = world.query_filtered::<&WorldEntity, With<PlayerMarker>>();
+        assert_eq!(players.single(&world).position, IVec2::Y);
+    }
+}
@@ -14,14 +14,48 @@ use bevy::{
 use bevy_rand::{prelude::WyRand, resource::GlobalEntropy};
 
 use funty::Unsigned;
-use rand_core::RngCore;
+use rand_core::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
 
 use super::GameStates;
 
-#[derive(Resource, Default)]
+// saved along with the run, so that a resumed or rewound run rolls the same as it would have
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
 pub struct Random(GlobalEntropy<WyRand>);
 
+// the seed of the run in progress, and the one the next restart should use (if any).
+// without a requested seed, a restart picks a fresh one from the current stream.
+#[derive(Resource, Default, Debug)]
+pub struct RunSeed {
+    pub current: u64,
+    pub requested: Option<u64>,
+}
+
+impl RunSeed {
+    pub fn from_args() -> Self {
+        let args = std::env::args().collect::<Vec<_>>();
+        let requested = args
+            .iter()
+            .position(|arg| arg == "--seed")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|seed| seed.parse::<u64>().ok());
+
+        RunSeed {
+            current: 0,
+            requested,
+        }
+    }
+}
+
 impl Random {
+    pub fn seeded(seed: u64) -> Self {
+        Random(GlobalEntropy::<WyRand>::seed_from_u64(seed))
+    }
+
+    pub fn next_seed(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
     pub fn from<T: Copy>(&mut self, arr: &[T]) -> T {
         arr[self.0.next_u32() as usize % arr.len()]
     }
@@ -42,10 +76,37 @@ impl Random {
         range.start + (self.0.next_u32() % (range.end - range.start) as u32) as i32
     }
 
+    // like `gen`, but for ranges written down in data files: a range of one value is just that value
+    pub fn roll(&mut self, (lo, hi): (i32, i32)) -> i32 {
+        if hi - lo <= 1 {
+            lo
+        } else {
+            self.gen(lo..hi)
+        }
+    }
+
     pub fn gen2d(&mut self, x: Range<i32>, y: Range<i32>) -> IVec2 {
         IVec2::new(self.gen(x), self.gen(y))
     }
 
+    // picks one of the choices, each as likely as its weight
+    pub fn weighted<T: Copy>(&mut self, choices: &[(T, u32)]) -> Option<T> {
+        let total = choices.iter().map(|(_, w)| *w as i32).sum::<i32>();
+        if choices.len() <= 1 || total <= 0 {
+            return choices.first().map(|(c, _)| *c);
+        }
+
+        let mut pick = self.gen(0..total);
+        for (choice, weight) in choices {
+            if pick < *weight as i32 {
+                return Some(*choice);
+            }
+            pick -= *weight as i32;
+        }
+
+        None
+    }
+
     pub fn shuffle<T>(&mut self, mut v: Vec<T>) -> Vec<T> {
         let l = v.len();
         for i in 0..l {
@@ -83,6 +144,7 @@ pub struct SvarogFeelPlugin;
 impl Plugin for SvarogFeelPlugin {
     fn build(&self, bevy: &mut bevy::prelude::App) {
         bevy.insert_resource(Random::default())
+            .insert_resource(RunSeed::from_args())
             .add_systems(Update, tween_size.run_if(in_state(GameStates::Game)));
     }
 }
@@ -7,6 +7,7 @@ use super::{
     health::Health,
     inventory::CarriedMarker,
     procgen::PlayerMarker,
+    status::{suffers, StatusEffect, StatusEffects, BLINDED_SIGHT},
 };
 
 #[derive(Event)]
@@ -15,6 +16,21 @@ pub struct RecalculateFOVEvent;
 #[derive(Component)]
 pub struct Sight(pub u32);
 
+// WIL and INT stretch (or shrink) how far anyone sees, but never below half of their sight
+pub fn sight_radius(sight: &Sight, character: &Character) -> usize {
+    let mut e = (character.willpower + character.intelligence).min(9);
+    let s = sight.0 as i32;
+    if e <= -s / 2 {
+        e = -s / 2;
+    }
+
+    let mut m = s + e;
+    if m < 0 {
+        m = 1;
+    }
+    m as usize
+}
+
 pub fn on_new_fov_added(
     query: Query<Added<FOV>>,
     mut recalc_event: EventWriter<RecalculateFOVEvent>,
@@ -25,10 +41,14 @@ pub fn on_new_fov_added(
 }
 
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 #[allow(clippy::collapsible_else_if)]
 pub fn recalculate_fov(
     mut recalc_event: EventReader<RecalculateFOVEvent>,
-    player_entity: Query<(&WorldEntity, &Health, &Sight, &Character), With<PlayerMarker>>,
+    player_entity: Query<
+        (&WorldEntity, &Health, &Sight, &Character, Option<&StatusEffects>),
+        With<PlayerMarker>,
+    >,
     grid: Option<Res<Grid>>,
     map: Option<ResMut<WorldData>>,
     mut non_players: Query<
@@ -54,7 +74,8 @@ pub fn recalculate_fov(
         return;
     };
 
-    let Ok((player_in_world, health, sight, character)) = &player_entity.get_single() else {
+    let Ok((player_in_world, health, sight, character, effects)) = &player_entity.get_single()
+    else {
         return;
     };
 
@@ -95,20 +116,13 @@ pub fn recalculate_fov(
     {
         let (x, y) = grid.norm(player_in_world.position);
 
-        let sight_affected_by_stats = {
-            let mut e = (character.willpower + character.intelligence).min(9);
-            let s = sight.0 as i32;
-            if e <= -s / 2 {
-                e = -s / 2;
-            }
-
-            let mut m = s + e;
-            if m < 0 {
-                m = 1;
-            }
-            m as usize
+        let radius = if suffers(*effects, Some(*health), StatusEffect::Blinded) {
+            BLINDED_SIGHT
+        } else {
+            sight_radius(sight, character)
         };
-        fov.compute_fov(&mut map.data, x, y, sight_affected_by_stats, true);
+
+        fov.compute_fov(&mut map.data, x, y, radius, true);
     }
 
     grid.entities.iter().for_each(|(pos, e)| {
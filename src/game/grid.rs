@@ -16,9 +16,10 @@ use bevy::{
     },
     sprite::{SpriteSheetBundle, TextureAtlas, TextureAtlasSprite},
     transform::components::Transform,
-    utils::{hashbrown::HashMap, HashSet},
+    utils::{HashMap, HashSet},
 };
 use doryen_fov::MapData;
+use serde::{Deserialize, Serialize};
 
 use crate::game::{GameAssets, GameStates};
 
@@ -36,7 +37,7 @@ use super::procgen::ClearLevel;
 #[derive(Component)]
 pub struct WorldEntityMarker;
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct WorldEntity {
     pub name: String,
     pub position: IVec2,
@@ -206,7 +207,7 @@ pub struct WorldData {
     pub blocking: HashMap<IVec2, Entity>,
 }
 
-#[derive(Component, Default, Clone, Copy, PartialEq)]
+#[derive(Component, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Passability {
     #[default]
     Passable,
@@ -243,11 +244,16 @@ impl Grid {
     }
 }
 
-fn create_grid_resource(mut commands: Commands, assets: Res<GameAssets>) {
+fn create_grid_resource(mut commands: Commands, assets: Option<Res<GameAssets>>) {
+    // headless runs never load the atlas, so the grid just holds an empty handle
+    let atlas = assets
+        .map(|assets| assets.atlas.clone_weak())
+        .unwrap_or_default();
+
     commands.insert_resource(Grid {
         size: IVec2::new(120, 62),
         tile: IVec2::new(16, 16),
-        atlas: assets.atlas.clone_weak(),
+        atlas,
         entities: Default::default(),
     });
 
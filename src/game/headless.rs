@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use super::{
+    ai::get_player,
+    player::PlayerState,
+    procgen::ProcGenEvent,
+    turns::TurnOrder,
+    GameStates, SvarogSimulationPlugin,
+};
+
+// paying for actions works the same for everyone, headless or not
+pub use super::actions::submit_action;
+
+// runs the simulation without a window, renderer, imgui or audio: good for tests, bots and
+// balance runs. drive it with `app.update()` and push actions through `submit_action`.
+pub struct SvarogHeadlessPlugin;
+
+impl Plugin for SvarogHeadlessPlugin {
+    fn build(&self, bevy: &mut App) {
+        bevy.add_plugins(MinimalPlugins)
+            .init_resource::<Input<KeyCode>>()
+            .add_state::<GameStates>()
+            .add_plugins(SvarogSimulationPlugin)
+            .add_systems(Startup, skip_asset_loading)
+            .add_systems(OnEnter(GameStates::Game), start_headless_run);
+    }
+}
+
+// there's nothing to load without a window, so go straight into setting up the grid
+fn skip_asset_loading(mut next_state: ResMut<NextState<GameStates>>) {
+    next_state.set(GameStates::Setup);
+}
+
+fn start_headless_run(
+    mut procgen_events: EventWriter<ProcGenEvent>,
+    mut player_state: ResMut<PlayerState>,
+) {
+    *player_state = PlayerState::Idle;
+    procgen_events.send(ProcGenEvent::RestartWorld);
+}
+
+// updates the app until the first level is generated and the player is in the turn order,
+// or gives up
+pub fn boot_headless(app: &mut App, max_updates: usize) -> Option<Entity> {
+    for _ in 0..max_updates {
+        app.update();
+
+        if let Some(player) = get_player(&mut app.world) {
+            if app.world.resource::<TurnOrder>().contains(player) {
+                return Some(player);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        actions::{a_wait, submit_action},
+        grid::WorldEntity,
+    };
+
+    #[test]
+    fn the_simulation_runs_without_a_window() {
+        let mut app = App::new();
+        app.add_plugins(SvarogHeadlessPlugin);
+
+        let player = boot_headless(&mut app, 20).expect("the first level never came up");
+        let turn = app.world.resource::<TurnOrder>().turn();
+        for _ in 0..5 {
+            assert!(submit_action(&mut app.world, a_wait(player)));
+            app.update();
+        }
+
+        assert!(app.world.resource::<TurnOrder>().turn() > turn);
+        assert!(app.world.get::<WorldEntity>(player).is_some());
+    }
+}
@@ -1,14 +1,18 @@
 use bevy::{ecs::component::Component, utils::HashMap};
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-use super::character::CharacterStat;
+use super::{character::CharacterStat, status::StatusEffect};
 
 #[derive(Component, Default)]
 pub struct RecoveryCounter(pub u32);
 
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct HitPoint {
     pub stat: Option<(CharacterStat, i32)>,
+    // an effect that lasts for as long as this hitpoint does
+    #[serde(default)]
+    pub status: Option<StatusEffect>,
 }
 
 impl HitPoint {
@@ -28,7 +32,7 @@ impl HitPoint {
     }
 }
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Health {
     pub size: usize,
     pub hitpoints: VecDeque<HitPoint>,
@@ -70,7 +74,10 @@ impl Health {
     pub fn enchanted_heal(&mut self, n: usize, typ: CharacterStat) {
         for _ in 0..n {
             if self.hitpoints.len() < self.size {
-                self.hitpoints.push_front(HitPoint { stat: Some((typ, -1)) });
+                self.hitpoints.push_front(HitPoint {
+                    stat: Some((typ, -1)),
+                    ..Default::default()
+                });
             }
         }
     }
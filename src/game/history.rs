@@ -1,23 +1,192 @@
-use bevy::{app::Plugin, ecs::system::Resource};
+use bevy::{
+    app::Plugin,
+    asset::{Asset, AssetEvent, Assets},
+    ecs::{
+        event::EventReader,
+        system::{Res, ResMut, Resource},
+    },
+    reflect::TypePath,
+    utils::HashMap,
+};
+use serde::Deserialize;
 
-#[derive(Resource, Default)]
-pub struct History(pub Vec<String>);
+use super::grid::WorldEntity;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TextEntry {
+    pub text: String,
+    // used when the line's subject is the player
+    #[serde(default)]
+    pub you: Option<String>,
+    // used when the line's count isn't 1
+    #[serde(default)]
+    pub plural: Option<String>,
+}
+
+#[derive(Asset, TypePath, Deserialize, Clone, Debug, Default)]
+pub struct TextCatalog {
+    #[serde(default)]
+    pub messages: HashMap<String, TextEntry>,
+    #[serde(default)]
+    pub writs: Vec<String>,
+}
+
+impl TextCatalog {
+    pub fn builtin() -> Self {
+        ron::from_str(include_str!("../../assets/hellth.text.ron"))
+            .expect("Expecting the builtin text catalog to parse")
+    }
+
+    // `--lang <code>` reads `assets/lang/<code>.text.ron`, to be laid over the builtin text
+    pub fn translation_from_args() -> Option<Self> {
+        let args = std::env::args().collect::<Vec<_>>();
+        let code = args
+            .iter()
+            .position(|arg| arg == "--lang")
+            .and_then(|index| args.get(index + 1))?;
+
+        let path = format!("assets/lang/{}.text.ron", code);
+        match std::fs::read_to_string(&path).map(|contents| ron::from_str::<Self>(&contents)) {
+            Ok(Ok(translation)) => Some(translation),
+            Ok(Err(err)) => {
+                println!("Couldn't parse translation {}: {}", path, err);
+                None
+            }
+            Err(err) => {
+                println!("Couldn't read translation {}: {}", path, err);
+                None
+            }
+        }
+    }
+
+    pub fn merged(mut self, over: Option<&TextCatalog>) -> Self {
+        if let Some(over) = over {
+            self.messages.extend(over.messages.clone());
+            if !over.writs.is_empty() {
+                self.writs = over.writs.clone();
+            }
+        }
+
+        self
+    }
+
+    pub fn get(&self, key: &str, args: &TextArgs) -> String {
+        let Some(entry) = self.messages.get(key) else {
+            return key.to_string();
+        };
+
+        let template = match (&entry.you, &entry.plural) {
+            (Some(you), _) if args.player => you,
+            (_, Some(plural)) if args.count.map(|c| c != 1).unwrap_or_default() => plural,
+            _ => &entry.text,
+        };
+
+        let mut line = template.clone();
+        for (name, value) in &args.values {
+            line = line.replace(&format!("{{{}}}", name), value);
+        }
+
+        line
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct TextArgs {
+    player: bool,
+    count: Option<i64>,
+    values: Vec<(&'static str, String)>,
+}
+
+pub fn args() -> TextArgs {
+    TextArgs::default()
+}
+
+impl TextArgs {
+    // who the line is about, filling in `{subject}` and picking the `you` variant for the player
+    pub fn subject(mut self, who: &WorldEntity) -> Self {
+        self.player = who.is_player;
+        self.with("subject", &who.name)
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.values.push((name, value.to_string()));
+        self
+    }
+
+    // fills in `{count}` and picks the `plural` variant if it isn't 1
+    pub fn count(mut self, count: impl Into<i64>) -> Self {
+        let count = count.into();
+        self.count = Some(count);
+        self.with("count", count)
+    }
+}
+
+#[derive(Resource)]
+pub struct History {
+    pub lines: Vec<String>,
+    pub text: TextCatalog,
+}
 
 pub type HistoryLog = History;
 
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            lines: vec![],
+            text: TextCatalog::builtin(),
+        }
+    }
+}
+
 impl History {
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.lines.clear();
     }
 
     pub fn add(&mut self, s: &str) {
-        self.0.push(s.to_string());
+        self.lines.push(s.to_string());
+    }
+
+    pub fn text(&self, key: &str, args: TextArgs) -> String {
+        self.text.get(key, &args)
+    }
+
+    // looks the line up in the text catalog and adds it to the log
+    pub fn say(&mut self, key: &str, args: TextArgs) {
+        let line = self.text(key, args);
+        self.add(&line);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Translation(pub Option<TextCatalog>);
+
+// the text asset is watched like the other catalogs, but lives inside the log
+pub fn sync_text_catalog(
+    mut events: EventReader<AssetEvent<TextCatalog>>,
+    assets: Res<Assets<TextCatalog>>,
+    translation: Res<Translation>,
+    mut log: ResMut<HistoryLog>,
+) {
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            if let Some(loaded) = assets.get(*id) {
+                log.text = loaded.clone().merged(translation.0.as_ref());
+                println!("Reloaded the text catalog");
+            }
+        }
     }
 }
 
 pub struct SvarogHistoryPlugin;
 impl Plugin for SvarogHistoryPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.init_resource::<History>();
+        let translation = Translation(TextCatalog::translation_from_args());
+
+        app.insert_resource(History {
+            lines: vec![],
+            text: TextCatalog::builtin().merged(translation.0.as_ref()),
+        })
+        .insert_resource(translation);
     }
 }
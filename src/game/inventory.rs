@@ -1,24 +1,31 @@
 use super::{
     character::CharacterStat,
+    feel::Random,
     grid::{Grid, WorldEntityBundle, WorldEntityKind},
     magic::Magic,
+    procgen::LevelDepth,
+    spells::Spell,
     sprites::Tile,
     ui::ShowEntityDetails,
 };
-use bevy::{prelude::*, render::view::RenderLayers, utils::HashMap};
+use bevy::{prelude::*, reflect::TypePath, render::view::RenderLayers, utils::HashMap};
 use bevy_mod_picking::{
     events::{Click, Pointer},
     prelude::On,
     PickableBundle,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Item {
     pub name: String,
     pub image: usize,
     pub item_type: ItemType,
     pub equip_stat_changes: Vec<(CharacterStat, i32)>,
+    // cast on whoever consumes the item, or wherever it breaks when thrown
+    #[serde(default)]
+    pub spell: Option<Spell>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -61,6 +68,12 @@ impl Debug for Item {
             desc.push(format!("{}{} {:?}", sign, val.abs(), stat));
         }
 
+        match self.spell {
+            Some(Spell::Afflict(effect)) => desc.push(format!("{:?}", effect).to_uppercase()),
+            Some(spell) => desc.push(format!("{:?}", spell).to_uppercase()),
+            None => {}
+        }
+
         f.write_fmt(format_args!("[{}]", desc.join(", ")))
     }
 }
@@ -77,7 +90,7 @@ pub struct CurrentlySelectedItem(pub Option<Entity>);
 #[derive(Component)]
 pub struct CarriedMarker;
 
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum ItemType {
     #[default]
     Unknown,
@@ -88,15 +101,153 @@ pub enum ItemType {
     Scroll,
 }
 
+// a (lo, hi) range as it is at depth 0, growing by `per_depth` with every level down
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct DepthRange {
+    pub range: (i32, i32),
+    #[serde(default)]
+    pub per_depth: (i32, i32),
+    #[serde(default)]
+    pub max: Option<i32>,
+}
+
+impl DepthRange {
+    pub fn at(&self, depth: u32) -> (i32, i32) {
+        let depth = depth as i32;
+        let lo = self.range.0 + self.per_depth.0 * depth;
+        let hi = self.range.1 + self.per_depth.1 * depth;
+        (lo, self.max.map(|max| hi.min(max)).unwrap_or(hi))
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct TemplateStat {
+    pub stat: CharacterStat,
+    pub value: i32,
+    #[serde(default)]
+    pub grows_every: Option<u32>,
+}
+
+impl TemplateStat {
+    pub fn at(&self, depth: u32) -> i32 {
+        self.value + self.grows_every.map(|n| (depth / n.max(1)) as i32).unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AffixTable {
+    pub count: DepthRange,
+    pub power: DepthRange,
+    #[serde(default)]
+    pub exclude: Vec<CharacterStat>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemTemplate {
+    pub name: String,
+    pub item_type: ItemType,
+    pub sprites: Vec<Tile>,
+    #[serde(default)]
+    pub stats: Vec<TemplateStat>,
+    #[serde(default)]
+    pub affixes: Option<String>,
+    #[serde(default)]
+    pub spell: Option<Spell>,
+}
+
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct ItemCatalog {
+    pub templates: HashMap<String, ItemTemplate>,
+    pub affixes: HashMap<String, AffixTable>,
+}
+
+impl ItemCatalog {
+    pub fn builtin() -> Self {
+        ron::from_str(include_str!("../../assets/hellth.items.ron"))
+            .expect("Expecting the builtin item catalog to parse")
+    }
+}
+
+impl Default for ItemCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
 #[derive(Default)]
 pub struct ItemBuilder {
     name: String,
     item_type: ItemType,
     tile: usize,
     stats: HashMap<CharacterStat, i32>,
+    spell: Option<Spell>,
 }
 
 impl ItemBuilder {
+    // the one way to roll an item: the template's own stats, then its affixes for this depth
+    pub fn from_template(
+        catalog: &ItemCatalog,
+        key: &str,
+        depth: u32,
+        rng: &mut Random,
+    ) -> Option<Self> {
+        let Some(template) = catalog.templates.get(key) else {
+            println!("No item template called '{}' in the catalog", key);
+            return None;
+        };
+
+        if template.sprites.is_empty() {
+            println!("Item template '{}' has no sprites", key);
+            return None;
+        }
+
+        let mut builder = ItemBuilder::default()
+            .with_name(&template.name)
+            .with_image(rng.from(&template.sprites))
+            .with_type(template.item_type.clone())
+            .with_spell(template.spell);
+
+        for stat in &template.stats {
+            builder = builder.with_stat(stat.stat, stat.at(depth));
+        }
+
+        let Some(affixes) = template.affixes.as_ref() else {
+            return Some(builder);
+        };
+
+        let Some(affixes) = catalog.affixes.get(affixes) else {
+            println!("No affix table called '{}' in the catalog", affixes);
+            return Some(builder);
+        };
+
+        let stats = [
+            CharacterStat::STR,
+            CharacterStat::ARC,
+            CharacterStat::INT,
+            CharacterStat::WIS,
+            CharacterStat::WIL,
+            CharacterStat::AGI,
+        ];
+
+        for _ in 0..rng.roll(affixes.count.at(depth)) {
+            let mut stat = rng.from(&stats);
+            let mut power = 0;
+            let mut attempt = 0;
+            while power == 0 || affixes.exclude.contains(&stat) {
+                power = rng.roll(affixes.power.at(depth));
+                stat = rng.from(&stats);
+                attempt += 1;
+                if attempt > 10 {
+                    break;
+                }
+            }
+
+            builder = builder.with_stat(stat, power);
+        }
+
+        Some(builder)
+    }
+
     pub fn with_name(mut self, name: &str) -> Self {
         self.name = name.to_string();
         self
@@ -120,6 +271,11 @@ impl ItemBuilder {
         self
     }
 
+    pub fn with_spell(mut self, spell: Option<Spell>) -> Self {
+        self.spell = spell;
+        self
+    }
+
     pub fn with_image(mut self, t: Tile) -> Self {
         self.tile = t.into();
         self
@@ -131,6 +287,7 @@ impl ItemBuilder {
             image: self.tile,
             item_type: self.item_type,
             equip_stat_changes: self.stats.into_iter().collect(),
+            spell: self.spell,
         }
     }
 
@@ -183,6 +340,7 @@ impl ItemBuilder {
                     image: self.tile,
                     item_type: self.item_type,
                     equip_stat_changes: self.stats.into_iter().collect(),
+                    spell: self.spell,
                 },
                 PickableBundle::default(),
                 On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
@@ -232,6 +390,7 @@ impl ItemBuilder {
                     image: self.tile,
                     item_type: self.item_type,
                     equip_stat_changes: self.stats.into_iter().collect(),
+                    spell: self.spell,
                 },
                 PickableBundle::default(),
                 On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
@@ -243,10 +402,24 @@ pub fn item() -> ItemBuilder {
     ItemBuilder::default()
 }
 
+// for actions and AI, which only have the world: rolls the template for the current depth
+pub fn item_from_template(world: &mut World, key: &str) -> Option<ItemBuilder> {
+    let depth = world
+        .get_resource::<LevelDepth>()
+        .map(|depth| depth.0)
+        .unwrap_or(1);
+
+    world.resource_scope(|world, catalog: Mut<ItemCatalog>| {
+        let mut rng = world.get_resource_mut::<Random>()?;
+        ItemBuilder::from_template(&catalog, key, depth, &mut rng)
+    })
+}
+
 pub struct SvarogInventoryPlugin;
 
 impl Plugin for SvarogInventoryPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CurrentlySelectedItem>();
+        app.init_resource::<CurrentlySelectedItem>()
+            .init_resource::<ItemCatalog>();
     }
 }
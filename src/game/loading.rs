@@ -1,20 +1,69 @@
-use bevy::app::Plugin;
+use bevy::{
+    app::{Plugin, Update},
+    asset::{Asset, AssetEvent, Assets},
+    ecs::{
+        event::EventReader,
+        schedule::{common_conditions::on_event, IntoSystemConfigs},
+        system::{Res, ResMut, Resource},
+    },
+};
 use bevy_asset_loader::{
     loading_state::{config::ConfigureLoadingState, LoadingState, LoadingStateAppExt},
     standard_dynamic_asset::StandardDynamicAssetCollection,
 };
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use super::{
+    history::{sync_text_catalog, TextCatalog},
+    inventory::ItemCatalog,
+    mobs::MonsterCatalog,
+    procgen::LevelCatalog,
+    GameAssets, GameStates,
+};
 
-use super::{GameAssets, GameStates};
+// data files live both as assets (so they can be watched and edited while the game runs)
+// and as resources (so the simulation doesn't need an asset server). this keeps them in step.
+fn sync_catalog<T: Asset + Resource + Clone>(
+    mut events: EventReader<AssetEvent<T>>,
+    assets: Res<Assets<T>>,
+    mut catalog: ResMut<T>,
+) {
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            if let Some(loaded) = assets.get(*id) {
+                *catalog = loaded.clone();
+                println!("Reloaded {}", std::any::type_name::<T>());
+            }
+        }
+    }
+}
 
 pub struct SvarogLoadingPlugin;
 
 impl Plugin for SvarogLoadingPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_state::<GameStates>().add_loading_state(
-            LoadingState::new(GameStates::AssetLoading)
-                .load_collection::<GameAssets>()
-                .with_dynamic_assets_file::<StandardDynamicAssetCollection>("resources.assets.ron")
-                .continue_to_state(GameStates::Setup),
-        );
+        app.add_plugins(RonAssetPlugin::<MonsterCatalog>::new(&["monsters.ron"]))
+            .add_plugins(RonAssetPlugin::<LevelCatalog>::new(&["levels.ron"]))
+            .add_plugins(RonAssetPlugin::<ItemCatalog>::new(&["items.ron"]))
+            .add_plugins(RonAssetPlugin::<TextCatalog>::new(&["text.ron"]))
+            .add_state::<GameStates>()
+            .add_loading_state(
+                LoadingState::new(GameStates::AssetLoading)
+                    .load_collection::<GameAssets>()
+                    .with_dynamic_assets_file::<StandardDynamicAssetCollection>(
+                        "resources.assets.ron",
+                    )
+                    .continue_to_state(GameStates::Setup),
+            )
+            .add_systems(
+                Update,
+                (
+                    sync_catalog::<MonsterCatalog>
+                        .run_if(on_event::<AssetEvent<MonsterCatalog>>()),
+                    sync_catalog::<LevelCatalog>.run_if(on_event::<AssetEvent<LevelCatalog>>()),
+                    sync_catalog::<ItemCatalog>.run_if(on_event::<AssetEvent<ItemCatalog>>()),
+                    sync_text_catalog.run_if(on_event::<AssetEvent<TextCatalog>>()),
+                ),
+            );
     }
 }
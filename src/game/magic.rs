@@ -4,21 +4,31 @@ use bevy::{
     app::{Plugin, Update},
     ecs::{
         component::Component,
+        entity::Entity,
+        event::EventWriter,
         query::With,
-        system::{Local, Query, ResMut, Resource},
+        system::{Local, Query, ResMut, Resource, SystemState},
+        world::World,
     },
     render::color::Color,
-    utils::hashbrown::HashMap,
+    utils::{HashMap, HashSet},
 };
+use serde::{Deserialize, Serialize};
 
 use super::{
+    actions::{a_dust, a_edge, a_lore, a_prison, a_regalia, AbstractAction, ActionEvent},
     character::{Character, CharacterStat},
     feel::Random,
-    history::HistoryLog,
+    grid::WorldEntity,
+    health::Health,
+    history::{args, HistoryLog},
     procgen::PlayerMarker,
 };
 
-const STATS: [CharacterStat; 6] = [
+// an aspect wakes up once both of the stats it's bound to reach this
+pub const ASPECT_THRESHOLD: i32 = 6;
+
+pub const STATS: [CharacterStat; 6] = [
     CharacterStat::STR,
     CharacterStat::ARC,
     CharacterStat::INT,
@@ -32,6 +42,7 @@ pub struct Focus(pub u32);
 
 pub type StatShorthand = String;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MagicAspect {
     Lore,       // summoning
     Edge,       // inflicts negatives around player
@@ -40,6 +51,65 @@ pub enum MagicAspect {
     Dust,       // steals health from others
 }
 
+const ASPECTS: [MagicAspect; 5] = [
+    MagicAspect::Lore,
+    MagicAspect::Edge,
+    MagicAspect::Prison,
+    MagicAspect::Regalia,
+    MagicAspect::Dust,
+];
+
+impl MagicAspect {
+    // what happens when the aspect bound to `stats` wakes up in `who`
+    pub fn action(&self, who: Entity, stats: [CharacterStat; 2]) -> AbstractAction {
+        match self {
+            MagicAspect::Lore => a_lore(who, stats.to_vec()),
+            MagicAspect::Edge => a_edge(who),
+            MagicAspect::Prison => a_prison(who),
+            MagicAspect::Regalia => a_regalia(who),
+            MagicAspect::Dust => a_dust(who),
+        }
+    }
+}
+
+// e.g. "STR+AGI", always in the order of `STATS`
+pub fn shorthand(a: CharacterStat, b: CharacterStat) -> StatShorthand {
+    format!("{:?}+{:?}", a, b)
+}
+
+// every two different stats, in the order of `STATS`
+fn stat_pairs() -> Vec<[CharacterStat; 2]> {
+    STATS
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| STATS[i + 1..].iter().map(move |b| [*a, *b]))
+        .collect()
+}
+
+// every creature but `who` within `range` tiles of it, nearest first
+pub fn creatures_around(world: &mut World, who: Entity, range: i32) -> Vec<Entity> {
+    let mut world_state = SystemState::<Query<(Entity, &WorldEntity), With<Health>>>::new(world);
+    let creatures = world_state.get(world);
+
+    let Ok((_, me)) = creatures.get(who) else {
+        return vec![];
+    };
+
+    let distance = |other: &WorldEntity| {
+        let d = (other.position - me.position).abs();
+        d.x.max(d.y)
+    };
+
+    let mut around = creatures
+        .iter()
+        .filter(|(other, world_entity)| *other != who && distance(world_entity) <= range)
+        .map(|(other, world_entity)| (other, distance(world_entity)))
+        .collect::<Vec<_>>();
+
+    around.sort_by_key(|(_, d)| *d);
+    around.into_iter().map(|(other, _)| other).collect()
+}
+
 // pub const MAGIC_ASPECT_SONGS: [&str; 5] = [
 //     "1/ We start through LORE, like stories of old,",
 //     "2/    through hardships up the knife, to the EDGE",
@@ -60,6 +130,8 @@ pub enum MagicAspect {
 pub struct Magic {
     pub color_bindings: HashMap<CharacterStat, Color>,
     pub aspects: HashMap<StatShorthand, MagicAspect>,
+    // each aspect only wakes up once a run, however often its stats dip and come back
+    pub awake: HashSet<MagicAspect>,
 }
 
 impl Magic {
@@ -79,12 +151,48 @@ impl Magic {
         let mapping: Vec<(CharacterStat, Color)> =
             stats.iter().zip(colors).map(|(c, s)| (*c, s)).collect();
 
+        // each run, every aspect is bound to a different two stats
+        let pairs = rng.shuffle(stat_pairs());
+        let aspects = pairs
+            .into_iter()
+            .zip(ASPECTS)
+            .map(|([a, b], aspect)| (shorthand(a, b), aspect));
+
         Self {
             color_bindings: HashMap::from_iter(mapping),
-            ..Default::default()
+            aspects: HashMap::from_iter(aspects),
+            awake: HashSet::new(),
         }
     }
 
+    // every aspect along with the two stats it's bound to, in the order of `STATS`
+    pub fn bindings(&self) -> Vec<(MagicAspect, [CharacterStat; 2])> {
+        stat_pairs()
+            .into_iter()
+            .filter_map(|[a, b]| {
+                let aspect = self.aspects.get(&shorthand(a, b))?;
+                Some((*aspect, [a, b]))
+            })
+            .collect()
+    }
+
+    // the aspects still asleep whose stats have both reached `ASPECT_THRESHOLD` going from `old` to `new`
+    pub fn awakened(
+        &self,
+        old: &Character,
+        new: &Character,
+    ) -> Vec<(MagicAspect, [CharacterStat; 2])> {
+        let reached = |character: &Character, [a, b]: [CharacterStat; 2]| {
+            character[a] >= ASPECT_THRESHOLD && character[b] >= ASPECT_THRESHOLD
+        };
+
+        self.bindings()
+            .into_iter()
+            .filter(|(aspect, _)| !self.awake.contains(aspect))
+            .filter(|(_, stats)| reached(new, *stats) && !reached(old, *stats))
+            .collect()
+    }
+
     pub fn reset(&mut self, rng: &mut ResMut<Random>) {
         *self = Self::new(rng);
     }
@@ -114,37 +222,113 @@ fn knowledge_checker(
     } else if let Some(old_state) = old.as_ref() {
         for stat in &player.learned {
             if !old_state.learned.contains(stat) {
-                log.add(&format!("You learned the color of {}. Check in your stat bar to see which color it is.", format!("{:?}", stat).to_uppercase()));
+                log.say("stat.learned", args().with("stat", format!("{:?}", stat).to_uppercase()));
             }
         }
 
         if (player.wisdom > 4 && player.arcana > 4)
             && (old_state.wisdom <= 4 || old_state.arcana <= 4)
         {
-            log.add("Because of your higher wisdom and arcana, you can discern some enemy stats.");
+            log.say("stat.discern", args());
         } else if (player.wisdom > 3 && player.arcana > 3)
             && (old_state.wisdom <= 3 || old_state.arcana <= 3)
         {
             player.learned.insert(CharacterStat::WIS);
             player.learned.insert(CharacterStat::ARC);
-            log.add("Because of your high WIS and ARC score, you now see the color of the strongest stat's in items and enemies!");
+            log.say("stat.auras", args());
         }
 
         if (old_state.wisdom > 3 && old_state.arcana > 3)
             && (player.wisdom <= 3 || player.arcana <= 3)
         {
-            log.add("Your perception grows bleak again - you can no longer see stats as colors.");
+            log.say("stat.bleak", args());
         }
 
         *old = Some(player.clone());
     }
 }
 
+// the player is respawned on restart and load, so a new run never compares against the last one
+fn aspect_checker(
+    player: Query<(Entity, &Character), With<PlayerMarker>>,
+    mut old: Local<Option<(Entity, Character)>>,
+    mut magic: ResMut<Magic>,
+    mut log: ResMut<HistoryLog>,
+    mut actions: EventWriter<ActionEvent>,
+) {
+    let Ok((entity, player)) = player.get_single() else {
+        return;
+    };
+
+    if let Some((_, old_state)) = old.as_ref().filter(|(was, _)| *was == entity) {
+        for (aspect, [a, b]) in magic.awakened(old_state, player) {
+            magic.awake.insert(aspect);
+            log.say(
+                "aspect.awakened",
+                args()
+                    .with("aspect", format!("{:?}", aspect).to_uppercase())
+                    .with("stats", format!("{:?} and {:?}", a, b)),
+            );
+            actions.send(ActionEvent(aspect.action(entity, [a, b])));
+        }
+    }
+
+    *old = Some((entity, player.clone()));
+}
+
 pub struct SvarogMagicPlugin;
 
 impl Plugin for SvarogMagicPlugin {
     fn build(&self, bevy: &mut bevy::prelude::App) {
         bevy.init_resource::<Magic>()
-            .add_systems(Update, knowledge_checker);
+            .add_systems(Update, (knowledge_checker, aspect_checker));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::System, prelude::*};
+
+    use super::*;
+    use crate::game::actions::tests::{spawn_fighter, test_world};
+
+    #[test]
+    fn aspects_only_wake_up_once_a_run() {
+        let mut world = test_world();
+        world.insert_resource(Magic {
+            aspects: [(shorthand(CharacterStat::STR, CharacterStat::AGI), MagicAspect::Edge)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        });
+        let nearly = Character {
+            strength: 6,
+            agility: 5,
+            ..Default::default()
+        };
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, nearly.clone(), 10);
+
+        let mut checker = IntoSystem::into_system(aspect_checker);
+        checker.initialize(&mut world);
+        let mut check = |world: &mut World, agility: i32, who: Entity| {
+            world.get_mut::<Character>(who).unwrap().agility = agility;
+            checker.run((), world);
+            world.resource::<Events<ActionEvent>>().len()
+        };
+
+        assert_eq!(check(&mut world, 5, player), 0);
+        assert_eq!(check(&mut world, 6, player), 1);
+        // putting on and taking off the same item doesn't wake it again
+        assert_eq!(check(&mut world, 5, player), 1);
+        assert_eq!(check(&mut world, 6, player), 1);
+        assert!(world.resource::<Magic>().awake.contains(&MagicAspect::Edge));
+
+        // a new run starts asleep, and doesn't look back at the last run's player
+        world.resource_mut::<Magic>().awake.clear();
+        world.despawn(player);
+        let player = spawn_fighter(&mut world, "You", IVec2::ONE, nearly, 10);
+        assert_eq!(check(&mut world, 6, player), 1);
+        assert_eq!(check(&mut world, 5, player), 1);
+        assert_eq!(check(&mut world, 6, player), 2);
     }
 }
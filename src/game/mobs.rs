@@ -1,294 +1,188 @@
 use super::{
-    ai::{AIAgent, AIStrategy, PendingActions},
-    character::Character,
+    ai::{
+        awareness::Awareness,
+        factions::Faction,
+        utility::{PlanDef, UtilityProfile},
+        AIAgent, AIStrategy, PendingActions,
+    },
+    character::{Character, CharacterStat},
     feel::Random,
+    fov::Sight,
     grid::{Grid, WorldEntityBundle, WorldEntityKind},
     health::{Health, RecoveryCounter},
     inventory::{CarriedItems, EquippedItems},
     magic::Focus,
-    sprites::*,
-    turns::TurnTaker,
+    sprites::Tile,
+    turns::{Speed, TurnTaker},
     ui::ShowEntityDetails,
 };
-use bevy::{prelude::*, render::view::RenderLayers};
+use bevy::{prelude::*, reflect::TypePath, render::view::RenderLayers, utils::HashMap};
 use bevy_mod_picking::{
     events::{Click, Pointer},
     prelude::On,
     PickableBundle,
 };
+use serde::Deserialize;
 
 #[derive(Component)]
 pub struct Mob;
 
-pub fn make_goblin(commands: &mut Commands, grid: &Res<Grid>, place: IVec2) {
-    commands
-        .spawn(WorldEntityBundle::new(
-            grid,
-            "Goblin",
-            place,
-            GOBLIN.into(),
-            true,
-            WorldEntityKind::NPC,
-            None,
-        ))
-        .with_children(|f| {
-            f.spawn(((
-                SpriteSheetBundle {
-                    sprite: TextureAtlasSprite::new(0),
-                    texture_atlas: grid.atlas.clone_weak(),
-                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
-                    ..Default::default()
-                },
-                RenderLayers::layer(1),
-            ),));
-        })
-        .insert((
-            TurnTaker,
-            Character { agility: 6, ..Default::default() },
-            Focus(0),
-            AIAgent::default(),
-            CarriedItems::default(),
-            EquippedItems::default(),
-            PendingActions::default(),
-            PickableBundle::default(),
-            RecoveryCounter::default(),
-            On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
-            Health::new(2),
-        ));
+#[derive(Component)]
+pub struct TheHealer;
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum StatBase {
+    Default,
+    Random,
 }
 
-pub fn make_orc(commands: &mut Commands, rng: &mut ResMut<Random>, grid: &Res<Grid>, place: IVec2, aggro: bool) {
-    let mut char = Character::random(rng);
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum StatRoll {
+    Set(CharacterStat, i32, i32),
+    Add(CharacterStat, i32, i32),
+    MaybeAdd(CharacterStat, i32, i32),
+}
 
-    commands
-        .spawn(WorldEntityBundle::new(
-            grid,
-            "Orc",
-            place,
-            ORC.into(),
-            true,
-            WorldEntityKind::NPC,
-            None,
-        ))
-        .with_children(|f| {
-            f.spawn(((
-                SpriteSheetBundle {
-                    sprite: TextureAtlasSprite::new(0),
-                    texture_atlas: grid.atlas.clone_weak(),
-                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
-                    ..Default::default()
-                },
-                RenderLayers::layer(1),
-            ),));
-        })
-        .insert((
-            Mob,
-            TurnTaker,
-            char,
-            Focus(0),
-            AIAgent(if aggro {
-                AIStrategy::Aggro
-            } else {
-                AIStrategy::Standard
-            }),
-            CarriedItems::default(),
-            EquippedItems::default(),
-            PendingActions::default(),
-            PickableBundle::default(),
-            RecoveryCounter::default(),
-            On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
-            Health::new(3),
-        ));
+#[derive(Deserialize, Clone, Debug)]
+pub struct StatsDef {
+    pub base: StatBase,
+    #[serde(default)]
+    pub rolls: Vec<StatRoll>,
 }
 
-pub fn make_acolyte(
-    commands: &mut Commands,
-    rng: &mut ResMut<Random>,
-    grid: &Res<Grid>,
-    place: IVec2,
-) {
-    let mut char = Character::random(rng);
-    char.arcana += 2;
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum HealthDef {
+    Fixed(usize),
+    // grows with the blood the player has sacrificed so far
+    Stash { base: i32, max: i32 },
+}
 
-    char.intelligence += rng.gen(0..3);
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum MonsterFlag {
+    Mob,
+    TheHealer,
+}
 
-    if rng.coin() {
-        char.strength += rng.gen(-2..1);
-    }
+#[derive(Deserialize, Clone, Debug)]
+pub struct MonsterDef {
+    pub name: String,
+    pub sprite: Tile,
+    pub stats: StatsDef,
+    pub focus: u32,
+    pub strategy: Vec<(AIStrategy, u32)>,
+    pub health: HealthDef,
+    #[serde(default = "default_sight")]
+    pub sight: u32,
+    // the key of the profile in `MonsterCatalog::utility` the `Utility` strategy thinks with
+    #[serde(default)]
+    pub profile: Option<String>,
+    // monsters without one never fight each other
+    #[serde(default)]
+    pub faction: Option<Faction>,
+    // in percent of how fast the player goes, 100 if left out
+    #[serde(default)]
+    pub speed: Option<u32>,
+    #[serde(default)]
+    pub flags: Vec<MonsterFlag>,
+}
 
-    if rng.coin() {
-        char.wisdom += rng.gen(-1..2);
-    }
+// as far as the player sees
+fn default_sight() -> u32 {
+    6
+}
 
-    commands
-        .spawn(WorldEntityBundle::new(
-            grid,
-            "Evoker",
-            place,
-            EVOKER.into(),
-            true,
-            WorldEntityKind::NPC,
-            None,
-        ))
-        .with_children(|f| {
-            f.spawn(((
-                SpriteSheetBundle {
-                    sprite: TextureAtlasSprite::new(0),
-                    texture_atlas: grid.atlas.clone_weak(),
-                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
-                    ..Default::default()
-                },
-                RenderLayers::layer(1),
-            ),));
-        })
-        .insert((
-            Mob,
-            TurnTaker,
-            char,
-            Focus(0),
-            AIAgent(AIStrategy::Caster),
-            CarriedItems::default(),
-            EquippedItems::default(),
-            PendingActions::default(),
-            PickableBundle::default(),
-            RecoveryCounter::default(),
-            On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
-            Health::new(5),
-        ));
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct MonsterCatalog {
+    pub monsters: HashMap<String, MonsterDef>,
+    #[serde(default)]
+    pub utility: HashMap<String, Vec<PlanDef>>,
 }
 
-pub fn make_thaumaturge(
-    commands: &mut Commands,
-    rng: &mut ResMut<Random>,
-    grid: &Res<Grid>,
-    place: IVec2,
-) {
-    let mut char = Character::random(rng);
-    char.arcana += 3;
-    char.wisdom += 2;
+impl MonsterCatalog {
+    // the same file the asset server watches, baked in so headless runs have monsters too
+    pub fn builtin() -> Self {
+        ron::from_str(include_str!("../../assets/hellth.monsters.ron"))
+            .expect("Expecting the builtin monster catalog to parse")
+    }
 
-    char.intelligence += rng.gen(2..3);
+    pub fn get(&self, key: &str) -> Option<&MonsterDef> {
+        self.monsters.get(key)
+    }
+}
 
-    if rng.coin() {
-        char.strength += rng.gen(-1..1);
+impl Default for MonsterCatalog {
+    fn default() -> Self {
+        Self::builtin()
     }
+}
 
-    commands
-        .spawn(WorldEntityBundle::new(
-            grid,
-            "Thaumaturge",
-            place,
-            THAUMATURGE.into(),
-            true,
-            WorldEntityKind::NPC,
-            None,
-        ))
-        .with_children(|f| {
-            f.spawn(((
-                SpriteSheetBundle {
-                    sprite: TextureAtlasSprite::new(0),
-                    texture_atlas: grid.atlas.clone_weak(),
-                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
-                    ..Default::default()
-                },
-                RenderLayers::layer(1),
-            ),));
-        })
-        .insert((
-            TurnTaker,
-            char,
-            Focus(4),
-            if rng.percent(80u32) {
-                AIAgent(AIStrategy::Caster)
-            } else {
-                AIAgent(AIStrategy::AggroCaster)
-            },
-            Mob,
-            CarriedItems::default(),
-            EquippedItems::default(),
-            PendingActions::default(),
-            PickableBundle::default(),
-            RecoveryCounter::default(),
-            On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
-            Health::new(4),
-        ));
+impl StatsDef {
+    pub fn roll(&self, rng: &mut ResMut<Random>) -> Character {
+        let mut char = match self.base {
+            StatBase::Default => Character::default(),
+            StatBase::Random => Character::random(rng),
+        };
+
+        for stat_roll in &self.rolls {
+            match *stat_roll {
+                StatRoll::Set(stat, lo, hi) => char[stat] = rng.roll((lo, hi)),
+                StatRoll::Add(stat, lo, hi) => char[stat] += rng.roll((lo, hi)),
+                StatRoll::MaybeAdd(stat, lo, hi) => {
+                    if rng.coin() {
+                        char[stat] += rng.roll((lo, hi));
+                    }
+                }
+            }
+        }
+
+        char
+    }
 }
 
-#[derive(Component)]
-pub struct TheHealer;
+impl MonsterDef {
+    pub fn pick_strategy(&self, rng: &mut ResMut<Random>) -> AIStrategy {
+        rng.weighted(&self.strategy).unwrap_or_default()
+    }
 
-pub fn make_healer(
+    pub fn health(&self, stash: i32) -> Health {
+        match self.health {
+            HealthDef::Fixed(hp) => Health::new(hp),
+            HealthDef::Stash { base, max } => Health::new((base + stash).clamp(0, max) as usize),
+        }
+    }
+}
+
+// spawns the monster with the given key from the catalog; `stash` only matters to monsters
+// whose health depends on it
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_monster(
     commands: &mut Commands,
     rng: &mut ResMut<Random>,
     grid: &Res<Grid>,
-    stash: i32,
+    catalog: &MonsterCatalog,
+    key: &str,
     place: IVec2,
-) {
-    let char = Character {
-        strength: 6,
-        arcana: 5,
-        intelligence: rng.gen(8..9),
-        wisdom: 6,
-        willpower: 10,
-        agility: rng.gen(8..9),
-        ..Default::default()
+    stash: i32,
+) -> Option<Entity> {
+    let Some(def) = catalog.get(key) else {
+        println!("No monster called '{}' in the catalog", key);
+        return None;
     };
 
-    commands
-        .spawn(WorldEntityBundle::new(
-            grid,
-            "The Healer",
-            place,
-            HEALER.into(),
-            true,
-            WorldEntityKind::NPC,
-            None,
-        ))
-        .with_children(|f| {
-            f.spawn(((
-                SpriteSheetBundle {
-                    sprite: TextureAtlasSprite::new(0),
-                    texture_atlas: grid.atlas.clone_weak(),
-                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
-                    ..Default::default()
-                },
-                RenderLayers::layer(1),
-            ),));
-        })
-        .insert((
-            TurnTaker,
-            char,
-            Focus(5),
-            AIAgent(AIStrategy::TheHealer),
-            CarriedItems::default(),
-            EquippedItems::default(),
-            PendingActions::default(),
-            PickableBundle::default(),
-            RecoveryCounter::default(),
-            On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
-            Health::new((10 + stash).clamp(0, 18) as usize),
-            TheHealer,
-        ));
-}
+    let char = def.stats.roll(rng);
+    let strategy = def.pick_strategy(rng);
 
-pub fn make_bat(commands: &mut Commands, rng: &mut ResMut<Random>, grid: &Res<Grid>, place: IVec2) {
-    let char = Character {
-        agility: rng.gen(8..10),
-        strength: rng.gen(3..6),
-        willpower: rng.gen(3..6),
-        ..Default::default()
-    };
+    let mut monster = commands.spawn(WorldEntityBundle::new(
+        grid,
+        &def.name,
+        place,
+        def.sprite.into(),
+        true,
+        WorldEntityKind::NPC,
+        None,
+    ));
 
-    commands
-        .spawn(WorldEntityBundle::new(
-            grid,
-            "Bat",
-            place,
-            BAT.into(),
-            true,
-            WorldEntityKind::NPC,
-            None,
-        ))
+    monster
         .with_children(|f| {
             f.spawn(((
                 SpriteSheetBundle {
@@ -301,17 +195,39 @@ pub fn make_bat(commands: &mut Commands, rng: &mut ResMut<Random>, grid: &Res<Gr
             ),));
         })
         .insert((
-            Mob,
             TurnTaker,
             char,
-            Focus(0),
-            AIAgent(AIStrategy::RandomMove),
+            Focus(def.focus),
+            AIAgent(strategy),
             CarriedItems::default(),
             EquippedItems::default(),
             PendingActions::default(),
             PickableBundle::default(),
             RecoveryCounter::default(),
             On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
-            Health::new(1),
+            def.health(stash),
+            Sight(def.sight),
+            Awareness::default(),
         ));
+
+    if let Some(profile) = &def.profile {
+        monster.insert(UtilityProfile(profile.clone()));
+    }
+
+    if let Some(faction) = def.faction {
+        monster.insert(faction);
+    }
+
+    if let Some(speed) = def.speed {
+        monster.insert(Speed(speed));
+    }
+
+    for flag in &def.flags {
+        match flag {
+            MonsterFlag::Mob => monster.insert(Mob),
+            MonsterFlag::TheHealer => monster.insert(TheHealer),
+        };
+    }
+
+    Some(monster.id())
 }
@@ -3,7 +3,12 @@ use std::time::Duration;
 use bevy::{ecs::system::Command, prelude::*};
 use bevy_kira_audio::prelude::*;
 
-use super::{actions::play_sfx, procgen::{LevelDepth, ProcGenEvent}, GameStates};
+use super::{
+    actions::{handle_gameplay_action, play_sfx},
+    outcomes::ActionOutcome,
+    procgen::{LevelCatalog, LevelDepth, PlayerMarker, ProcGenEvent},
+    GameStates,
+};
 
 fn play_music(asset_server: Res<AssetServer>, audio: Res<Audio>, mut settings: ResMut<GameAudioSettings>) {
     let bgm = audio.play(asset_server.load("sounds/the_pit.ogg"))
@@ -53,16 +58,48 @@ impl Command for SfxRevCommand {
     }
 }
 
+// every sound an action makes, played at most once per frame no matter how many made it
+fn outcome_sfx(
+    mut outcomes: EventReader<ActionOutcome>,
+    player: Query<(), With<PlayerMarker>>,
+    mut commands: Commands,
+) {
+    let mut sounds = vec![];
+    for outcome in outcomes.read() {
+        let sound = match *outcome {
+            ActionOutcome::Moved { .. } => "gameplay_step",
+            ActionOutcome::Dodged { .. } | ActionOutcome::Healed { .. } => "gameplay_surprise",
+            ActionOutcome::Teleported { .. } => "gameplay_surprise",
+            ActionOutcome::Focused { who } if player.contains(who) => "gameplay_surprise",
+            ActionOutcome::Damaged { .. } => "gameplay_hit",
+            ActionOutcome::Killed { player: false, .. } => "gameplay_kill",
+            ActionOutcome::Killed { player: true, .. } => "gameplay_death",
+            ActionOutcome::ItemPickedUp { who, .. } if player.contains(who) => "item_pickup",
+            ActionOutcome::ItemConsumed { .. } | ActionOutcome::SpellCast { .. } => "item_cast",
+            _ => continue,
+        };
+
+        if !sounds.contains(&sound) {
+            sounds.push(sound);
+        }
+    }
+
+    for name in sounds {
+        commands.add(SfxCommand { name: name.to_string() });
+    }
+}
+
 fn change_music(
         asset_server: Res<AssetServer>, 
         audio: Res<Audio>, 
         mut settings: ResMut<GameAudioSettings>, 
         depth: Res<LevelDepth>, 
+        levels: Res<LevelCatalog>,
         mut procgen_events: EventReader<ProcGenEvent>,
         mut audio_instances: ResMut<Assets<AudioInstance>>) {
 
     for e in procgen_events.read() {
-        if *e == ProcGenEvent::NextLevel && depth.0 == 5 {
+        if *e == ProcGenEvent::NextLevel && levels.is_boss_level(depth.0) {
             if let Some(music_instance) = settings.music.as_ref() {
                 if let Some(music) = audio_instances.get_mut(music_instance) {
                     music.set_volume(0.0, AudioTween::linear(Duration::from_secs_f32(2.0)));
@@ -97,7 +134,7 @@ fn change_music(
     }
 }
 
-fn control_audio(audio: Res<Audio>, input: Res<Input<KeyCode>>, mut audio_settings: ResMut<GameAudioSettings>, mut audio_instances: ResMut<Assets<AudioInstance>>) {
+fn control_audio(input: Res<Input<KeyCode>>, mut audio_settings: ResMut<GameAudioSettings>, mut audio_instances: ResMut<Assets<AudioInstance>>) {
     let mut dv = 0.0;
     if input.just_pressed(KeyCode::Plus) || input.just_pressed(KeyCode::Equals) {
         dv = 1.0;
@@ -125,6 +162,7 @@ impl Plugin for SvarogMusicPlugin {
             .init_resource::<GameAudioSettings>()
             .add_systems(Startup, play_music)
             .add_systems(Update, control_audio)
+            .add_systems(Update, outcome_sfx.after(handle_gameplay_action).run_if(on_event::<ActionOutcome>()))
             .add_systems(Update, change_music.run_if(in_state(GameStates::Game)).run_if(on_event::<ProcGenEvent>()));
     }
 }
\ No newline at end of file
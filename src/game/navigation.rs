@@ -0,0 +1,343 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy::{
+    app::{App, Plugin},
+    ecs::{
+        entity::Entity,
+        query::Without,
+        system::{Query, Res, ResMut, Resource, SystemParam},
+    },
+    math::IVec2,
+    utils::HashMap,
+};
+use pathfinding::prelude::astar;
+
+use super::{
+    ai::items::worth_taking,
+    grid::{Grid, WorldData, WorldEntity},
+    inventory::{CarriedMarker, Item},
+};
+
+// how many paths are kept around before the cache is thrown away and rebuilt
+const MAX_CACHED_PATHS: usize = 4096;
+
+// how far from the player casters like to stand
+pub const CASTER_RANGE: i32 = 7;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
+// paths around the terrain of the current level. they only know about `WorldData::solid`,
+// which only changes when the level does (see `ChangePassability`), so they're cached until
+// then. creatures in the way are walked around on demand, without caching.
+#[derive(Resource, Default)]
+pub struct Navigation {
+    paths: HashMap<(IVec2, IVec2), Vec<IVec2>>,
+    maps: Option<NavigationMaps>,
+}
+
+impl Navigation {
+    pub fn invalidate(&mut self) {
+        self.paths.clear();
+        self.maps = None;
+    }
+
+    // the level-wide maps, recomputed whenever the player or anything lying around has moved
+    // since they were last made
+    pub fn maps(
+        &mut self,
+        grid: &Grid,
+        world_data: &WorldData,
+        player: IVec2,
+        mut items: Vec<IVec2>,
+    ) -> &NavigationMaps {
+        items.sort_by_key(|item| (item.x, item.y));
+        let stale = self
+            .maps
+            .as_ref()
+            .map(|maps| maps.player != player || maps.items != items)
+            .unwrap_or(true);
+
+        if stale {
+            self.maps = Some(NavigationMaps::new(grid, world_data, player, items));
+        }
+
+        self.maps.as_ref().unwrap()
+    }
+
+    // the next tile to step onto to get from `from` to `to`, walking around walls and any
+    // creature in the way. the goal itself may be occupied (that's usually who's being chased).
+    pub fn next_step(
+        &mut self,
+        grid: &Grid,
+        world_data: &WorldData,
+        from: IVec2,
+        to: IVec2,
+    ) -> Option<IVec2> {
+        if from == to {
+            return None;
+        }
+
+        let path = self.terrain_path(grid, world_data, from, to)?;
+        let step = *path.get(1)?;
+
+        if step == to || !world_data.blocking.contains_key(&step) {
+            // the rest of the path is just as good from the next tile
+            if path.len() > 2 {
+                self.paths.insert((step, to), path[1..].to_vec());
+            }
+
+            return Some(step);
+        }
+
+        find_path(grid, world_data, from, to, true).and_then(|path| path.get(1).copied())
+    }
+
+    fn terrain_path(
+        &mut self,
+        grid: &Grid,
+        world_data: &WorldData,
+        from: IVec2,
+        to: IVec2,
+    ) -> Option<Vec<IVec2>> {
+        if let Some(path) = self.paths.get(&(from, to)) {
+            return Some(path.clone());
+        }
+
+        let path = find_path(grid, world_data, from, to, false)?;
+        if self.paths.len() >= MAX_CACHED_PATHS {
+            self.paths.clear();
+        }
+
+        self.paths.insert((from, to), path.clone());
+        Some(path)
+    }
+}
+
+pub fn is_walkable(grid: &Grid, world_data: &WorldData, position: IVec2) -> bool {
+    grid.get(position).is_some() && !world_data.solid.contains(&position)
+}
+
+fn neighbours<'a>(
+    grid: &'a Grid,
+    world_data: &'a WorldData,
+    position: IVec2,
+    avoid: Option<&'a HashMap<IVec2, Entity>>,
+    goal: IVec2,
+) -> impl Iterator<Item = (IVec2, u32)> + 'a {
+    NEIGHBOURS.iter().filter_map(move |delta| {
+        let next = position + *delta;
+        let free = avoid.map(|blocking| next == goal || !blocking.contains_key(&next));
+        if is_walkable(grid, world_data, next) && free.unwrap_or(true) {
+            Some((next, 1))
+        } else {
+            None
+        }
+    })
+}
+
+// A* over the level, with diagonal steps costing the same as straight ones (as they do in
+// `MoveAction`). includes both ends.
+pub fn find_path(
+    grid: &Grid,
+    world_data: &WorldData,
+    from: IVec2,
+    to: IVec2,
+    avoid_blocking: bool,
+) -> Option<Vec<IVec2>> {
+    let avoid = avoid_blocking.then_some(&world_data.blocking);
+    astar(
+        &from,
+        |position| neighbours(grid, world_data, *position, avoid, to),
+        |position| {
+            let d = (*position - to).abs();
+            d.x.max(d.y) as u32
+        },
+        |position| *position == to,
+    )
+    .map(|(path, _)| path)
+}
+
+// how far every walkable tile on the level is from something, in steps. creatures roll
+// downhill on it to get closer to whatever it was made from.
+#[derive(Default, Clone)]
+pub struct DijkstraMap(HashMap<IVec2, i32>);
+
+impl DijkstraMap {
+    // every seeded tile starts out at its value and the rest of the level is filled in from
+    // there, so that no tile is more than a step above its lowest neighbour
+    pub fn from_values(
+        grid: &Grid,
+        world_data: &WorldData,
+        seeds: impl IntoIterator<Item = (IVec2, i32)>,
+    ) -> Self {
+        let mut values = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for (position, value) in seeds {
+            if is_walkable(grid, world_data, position) {
+                values.insert(position, value);
+                queue.push(Reverse((value, position.x, position.y)));
+            }
+        }
+
+        while let Some(Reverse((value, x, y))) = queue.pop() {
+            let position = IVec2::new(x, y);
+            if values.get(&position).map(|v| *v < value).unwrap_or_default() {
+                continue;
+            }
+
+            for (next, cost) in neighbours(grid, world_data, position, None, position) {
+                let next_value = value + cost as i32;
+                if values.get(&next).map(|v| *v > next_value).unwrap_or(true) {
+                    values.insert(next, next_value);
+                    queue.push(Reverse((next_value, next.x, next.y)));
+                }
+            }
+        }
+
+        DijkstraMap(values)
+    }
+
+    pub fn from_goals(grid: &Grid, world_data: &WorldData, goals: &[IVec2]) -> Self {
+        Self::from_values(grid, world_data, goals.iter().map(|goal| (*goal, 0)))
+    }
+
+    // running downhill on the plain inverse only ever ends up in the nearest corner: scaling it
+    // up and filling it in again makes it worth running past the threat to get somewhere safer
+    pub fn inverted(&self, grid: &Grid, world_data: &WorldData) -> Self {
+        Self::from_values(
+            grid,
+            world_data,
+            self.0.iter().map(|(position, value)| (*position, -(value * 12) / 10)),
+        )
+    }
+
+    // lowest where the distance on this map is `range`
+    pub fn at_range(&self, grid: &Grid, world_data: &WorldData, range: i32) -> Self {
+        Self::from_values(
+            grid,
+            world_data,
+            self.0.iter().map(|(position, value)| (*position, (value - range).abs())),
+        )
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<i32> {
+        self.0.get(&position).copied()
+    }
+
+    // the free neighbouring tile that's the lowest on the map, if it's lower than `from`
+    pub fn downhill(&self, from: IVec2, blocking: &HashMap<IVec2, Entity>) -> Option<IVec2> {
+        let mut best = (from, self.get(from)?);
+        for delta in NEIGHBOURS {
+            let next = from + delta;
+            if blocking.contains_key(&next) {
+                continue;
+            }
+
+            if let Some(value) = self.get(next) {
+                if value < best.1 {
+                    best = (next, value);
+                }
+            }
+        }
+
+        (best.0 != from).then_some(best.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum NavigationGoal {
+    // get closer to the player
+    Player,
+    // get away from the player, without getting cornered
+    Flee,
+    // keep at `CASTER_RANGE` from the player
+    Kite,
+    // get to the nearest item worth taking that's lying around
+    Items,
+}
+
+pub struct NavigationMaps {
+    pub player: IVec2,
+    pub items: Vec<IVec2>,
+    pub to_player: DijkstraMap,
+    pub flee: DijkstraMap,
+    pub kite: DijkstraMap,
+    pub to_items: DijkstraMap,
+}
+
+impl NavigationMaps {
+    pub fn new(grid: &Grid, world_data: &WorldData, player: IVec2, items: Vec<IVec2>) -> Self {
+        let to_player = DijkstraMap::from_goals(grid, world_data, &[player]);
+
+        NavigationMaps {
+            player,
+            flee: to_player.inverted(grid, world_data),
+            kite: to_player.at_range(grid, world_data, CASTER_RANGE),
+            to_items: DijkstraMap::from_goals(grid, world_data, &items),
+            to_player,
+            items,
+        }
+    }
+
+    pub fn get(&self, goal: NavigationGoal) -> &DijkstraMap {
+        match goal {
+            NavigationGoal::Player => &self.to_player,
+            NavigationGoal::Flee => &self.flee,
+            NavigationGoal::Kite => &self.kite,
+            NavigationGoal::Items => &self.to_items,
+        }
+    }
+}
+
+// everything an action needs to find its way around the level
+#[derive(SystemParam)]
+pub struct NavigationParams<'w, 's> {
+    pub grid: Res<'w, Grid>,
+    pub world_data: Res<'w, WorldData>,
+    pub navigation: ResMut<'w, Navigation>,
+    pub items: Query<'w, 's, (&'static WorldEntity, &'static Item), Without<CarriedMarker>>,
+}
+
+impl NavigationParams<'_, '_> {
+    pub fn next_step(&mut self, from: IVec2, to: IVec2) -> Option<IVec2> {
+        self.navigation.next_step(&self.grid, &self.world_data, from, to)
+    }
+
+    pub fn loose_items(&self) -> Vec<IVec2> {
+        self.items
+            .iter()
+            .filter(|(_, item)| worth_taking(item))
+            .map(|(world_entity, _)| world_entity.position)
+            .collect()
+    }
+
+    // how many steps it is to the nearest of `loose_items`
+    pub fn steps_to_items(&mut self, from: IVec2, player: IVec2) -> Option<i32> {
+        let items = self.loose_items();
+        let maps = self.navigation.maps(&self.grid, &self.world_data, player, items);
+        maps.get(NavigationGoal::Items).get(from)
+    }
+
+    pub fn downhill(&mut self, goal: NavigationGoal, from: IVec2, player: IVec2) -> Option<IVec2> {
+        let items = self.loose_items();
+        let maps = self.navigation.maps(&self.grid, &self.world_data, player, items);
+        maps.get(goal).downhill(from, &self.world_data.blocking)
+    }
+}
+
+pub struct SvarogNavigationPlugin;
+
+impl Plugin for SvarogNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Navigation>();
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    actions::handle_gameplay_action,
+    ai::awareness::Awareness,
+    character::Character,
+    grid::{Grid, WorldData, WorldEntity},
+    navigation::is_walkable,
+    procgen::PlayerMarker,
+};
+
+// the loud things creatures do
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Noise {
+    Step,
+    Hit,
+    Chant,
+    // a call for help: the only noise monsters come running to when another monster makes it
+    Yell,
+}
+
+impl Noise {
+    // how many steps away the noise can still be heard. the nimble step lightly.
+    pub fn loudness(&self, character: Option<&Character>) -> u32 {
+        match self {
+            Noise::Step => {
+                let agility = character.map(|c| c.agility).unwrap_or_default();
+                (5 - agility / 2).clamp(1, 5) as u32
+            }
+            Noise::Hit => 5,
+            Noise::Chant => 8,
+            Noise::Yell => 14,
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct NoiseEvent {
+    pub source: Entity,
+    pub at: IVec2,
+    pub noise: Noise,
+    pub loudness: u32,
+    pub by_player: bool,
+}
+
+// called from actions, next to their sound effects
+pub fn make_noise(world: &mut World, who: Entity, noise: Noise) {
+    let Some(world_entity) = world.get::<WorldEntity>(who) else {
+        return;
+    };
+
+    let event = NoiseEvent {
+        source: who,
+        at: world_entity.position,
+        noise,
+        loudness: noise.loudness(world.get::<Character>(who)),
+        by_player: world.get::<PlayerMarker>(who).is_some(),
+    };
+
+    if let Some(mut events) = world.get_resource_mut::<Events<NoiseEvent>>() {
+        events.send(event);
+    }
+}
+
+// how many steps it takes the noise to reach every tile it reaches. it goes around walls
+// rather than through them, and doesn't care who's standing in the way.
+pub fn propagate(
+    grid: &Grid,
+    world_data: &WorldData,
+    at: IVec2,
+    loudness: u32,
+) -> HashMap<IVec2, u32> {
+    let mut heard = HashMap::new();
+    let mut queue = VecDeque::new();
+    heard.insert(at, 0);
+    queue.push_back(at);
+
+    while let Some(position) = queue.pop_front() {
+        let steps = heard[&position];
+        if steps >= loudness {
+            continue;
+        }
+
+        for x in -1..=1 {
+            for y in -1..=1 {
+                let next = position + IVec2::new(x, y);
+                if heard.contains_key(&next) || !is_walkable(grid, world_data, next) {
+                    continue;
+                }
+
+                heard.insert(next, steps + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    heard
+}
+
+pub fn hear_noises(
+    mut noises: EventReader<NoiseEvent>,
+    grid: Res<Grid>,
+    world_data: Res<WorldData>,
+    mut listeners: Query<(Entity, &WorldEntity, &mut Awareness)>,
+) {
+    for noise in noises.read() {
+        if !noise.by_player && noise.noise != Noise::Yell {
+            continue;
+        }
+
+        let heard = propagate(&grid, &world_data, noise.at, noise.loudness);
+        for (entity, world_entity, mut awareness) in &mut listeners {
+            if entity != noise.source && heard.contains_key(&world_entity.position) {
+                *awareness = awareness.hear(noise.at);
+            }
+        }
+    }
+}
+
+pub struct SvarogNoisePlugin;
+
+impl Plugin for SvarogNoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NoiseEvent>().add_systems(
+            Update,
+            hear_noises
+                .after(handle_gameplay_action)
+                .run_if(on_event::<NoiseEvent>()),
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    actions::handle_gameplay_action,
+    character::CharacterStat,
+    procgen::{PlayerMarker, ProcGenEvent},
+};
+
+// what came of an action, for whoever wants to know: sounds, screen shake, achievements and
+// statistics all listen for these instead of being done by the actions themselves
+#[derive(Event, Clone, Debug, PartialEq)]
+pub enum ActionOutcome {
+    Moved {
+        who: Entity,
+        from: IVec2,
+        to: IVec2,
+    },
+    // moved without walking there, by a blink
+    Teleported {
+        who: Entity,
+        from: IVec2,
+        to: IVec2,
+    },
+    Dodged {
+        attacker: Entity,
+        target: Entity,
+    },
+    Damaged {
+        attacker: Entity,
+        target: Entity,
+        amount: usize,
+        sneak: bool,
+    },
+    // monsters are gone by the time anyone hears about this, so it says whether it was the player
+    Killed {
+        who: Entity,
+        player: bool,
+    },
+    Healed {
+        who: Entity,
+    },
+    Focused {
+        who: Entity,
+    },
+    ItemPickedUp {
+        who: Entity,
+        what: Entity,
+    },
+    ItemEquipped {
+        who: Entity,
+        what: Entity,
+    },
+    ItemConsumed {
+        who: Entity,
+        what: Entity,
+    },
+    // a hitpoint took on (or gave up) some of a stat, from bones or from a curse
+    HitPointEnchanted {
+        who: Entity,
+        stat: CharacterStat,
+        amount: i32,
+    },
+    // a curse, or one of the aspects
+    SpellCast {
+        who: Entity,
+    },
+    // a curse that didn't take, thanks to arcana or an aura
+    SpellResisted {
+        who: Entity,
+    },
+}
+
+// what actions report while they run, sent out once each of them is done
+#[derive(Resource, Default)]
+pub struct PendingOutcomes(pub Vec<ActionOutcome>);
+
+pub fn report(world: &mut World, outcome: ActionOutcome) {
+    world
+        .get_resource_or_insert_with(PendingOutcomes::default)
+        .0
+        .push(outcome);
+}
+
+// called by `handle_gameplay_action` after every action
+pub fn send_outcomes(world: &mut World) {
+    let Some(mut pending) = world.get_resource_mut::<PendingOutcomes>() else {
+        return;
+    };
+
+    let outcomes = std::mem::take(&mut pending.0);
+    if let Some(mut events) = world.get_resource_mut::<Events<ActionOutcome>>() {
+        events.extend(outcomes);
+    }
+}
+
+// how the current run has gone so far, for the end screens
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct RunStatistics {
+    pub steps: u32,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    // monsters that died, at anyone's hand
+    pub fallen: u32,
+    pub items_consumed: u32,
+    pub spells_resisted: u32,
+}
+
+pub fn tally_outcomes(
+    mut outcomes: EventReader<ActionOutcome>,
+    mut statistics: ResMut<RunStatistics>,
+    player: Query<(), With<PlayerMarker>>,
+) {
+    for outcome in outcomes.read() {
+        match *outcome {
+            ActionOutcome::Moved { who, .. } if player.contains(who) => statistics.steps += 1,
+            ActionOutcome::Damaged { attacker, amount, .. } if player.contains(attacker) => {
+                statistics.damage_dealt += amount as u32;
+            }
+            ActionOutcome::Damaged { target, amount, .. } if player.contains(target) => {
+                statistics.damage_taken += amount as u32;
+            }
+            ActionOutcome::Killed { player: false, .. } => statistics.fallen += 1,
+            ActionOutcome::ItemConsumed { who, .. } if player.contains(who) => {
+                statistics.items_consumed += 1;
+            }
+            ActionOutcome::SpellResisted { who } if player.contains(who) => {
+                statistics.spells_resisted += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn reset_statistics(
+    mut procgen_events: EventReader<ProcGenEvent>,
+    mut statistics: ResMut<RunStatistics>,
+) {
+    for procgen in procgen_events.read() {
+        if *procgen == ProcGenEvent::RestartWorld {
+            *statistics = RunStatistics::default();
+        }
+    }
+}
+
+pub struct SvarogOutcomesPlugin;
+
+impl Plugin for SvarogOutcomesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ActionOutcome>()
+            .init_resource::<PendingOutcomes>()
+            .init_resource::<RunStatistics>()
+            .add_systems(
+                Update,
+                tally_outcomes
+                    .after(handle_gameplay_action)
+                    .run_if(on_event::<ActionOutcome>()),
+            )
+            .add_systems(Update, reset_statistics.run_if(on_event::<ProcGenEvent>()));
+    }
+}
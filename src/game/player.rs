@@ -4,17 +4,16 @@ use bevy::{
     render::{camera::CameraUpdateSystem, view::RenderLayers},
     transform::TransformSystem,
 };
-use bevy_kira_audio::Audio;
 
 use crate::game::actions::{a_drop, a_move};
 
 use super::{
     actions::{
-        a_consume, a_descend, a_equip, a_focus, a_fortune, a_pickup, a_throw, a_unequip, a_wait, play_sfx, ActionEvent
-    }, ai::PendingActions, character::Character, feel::{Random, Targeting, TweenSize}, grid::{Grid, WorldData, WorldEntity}, health::Health, history::HistoryLog, inventory::{
+        a_consume, a_descend, a_equip, a_focus, a_fortune, a_pickup, a_sacrifice, a_throw, a_unequip, a_wait, handle_gameplay_action, ActionEvent, SubmitAction
+    }, ai::PendingActions, feel::{Random, RunSeed, Targeting, TweenSize}, grid::{Grid, WorldEntity}, health::Health, history::{args, HistoryLog}, inventory::{
         CarriedItems, CarriedMarker, CurrentlySelectedItem, EquippedItems, Item, ItemActions,
         ItemType,
-    }, music::{SfxCommand, SfxRevCommand}, procgen::{LevelDepth, PlayerMarker, ProcGenEvent}, sprites::{OCTOPUS, TARGET}, turns::{TurnCounter, TurnOrder}, GameStates
+    }, music::{SfxCommand, SfxRevCommand}, outcomes::ActionOutcome, procgen::{generate_level, Altar, LevelCatalog, LevelDepth, PlayerMarker, ProcGenEvent}, sprites::{OCTOPUS, TARGET}, turns::{TurnCounter, TurnOrder}, GameStates
 };
 
 #[derive(Resource, Default, Debug, PartialEq)]
@@ -37,6 +36,10 @@ pub enum PlayerState {
     Exiting,
     Shutdown,
     Reading(Entity),
+    EnteringSeed {
+        seed: String,
+        ascended: bool,
+    },
 }
 
 fn try_item_keys(keys: &Res<Input<KeyCode>>) -> Option<usize> {
@@ -63,6 +66,26 @@ fn try_item_keys(keys: &Res<Input<KeyCode>>) -> Option<usize> {
     }
 }
 
+fn try_digit_keys(keys: &Res<Input<KeyCode>>) -> Option<char> {
+    const DIGITS: [(KeyCode, KeyCode); 10] = [
+        (KeyCode::Key0, KeyCode::Numpad0),
+        (KeyCode::Key1, KeyCode::Numpad1),
+        (KeyCode::Key2, KeyCode::Numpad2),
+        (KeyCode::Key3, KeyCode::Numpad3),
+        (KeyCode::Key4, KeyCode::Numpad4),
+        (KeyCode::Key5, KeyCode::Numpad5),
+        (KeyCode::Key6, KeyCode::Numpad6),
+        (KeyCode::Key7, KeyCode::Numpad7),
+        (KeyCode::Key8, KeyCode::Numpad8),
+        (KeyCode::Key9, KeyCode::Numpad9),
+    ];
+
+    DIGITS
+        .iter()
+        .position(|(key, numpad)| keys.just_pressed(*key) || keys.just_pressed(*numpad))
+        .and_then(|digit| char::from_digit(digit as u32, 10))
+}
+
 fn try_direction_keys(keys: &Res<Input<KeyCode>>) -> Option<IVec2> {
     let shift = keys.pressed(KeyCode::ShiftLeft);
     if (shift && (keys.pressed(KeyCode::Numpad8) || keys.pressed(KeyCode::W))) || keys.just_pressed(KeyCode::W) || keys.just_pressed(KeyCode::Numpad8) {
@@ -94,6 +117,65 @@ pub fn on_shutdown(player_state: Res<PlayerState>, mut exit: EventWriter<AppExit
     }
 }
 
+// from the death or victory screen, R lets you type in a seed to restart from
+pub fn seed_prompt_controls(
+    keys: Res<Input<KeyCode>>,
+    mut procgen_events: EventWriter<ProcGenEvent>,
+    mut turn_counter: ResMut<TurnCounter>,
+    mut run_seed: ResMut<RunSeed>,
+    mut player_state: ResMut<PlayerState>,
+) {
+    let mut next_state = None;
+
+    match player_state.as_mut() {
+        PlayerState::Dead if keys.just_pressed(KeyCode::R) => {
+            next_state = Some(PlayerState::EnteringSeed {
+                seed: String::new(),
+                ascended: false,
+            });
+        }
+
+        PlayerState::Ascended if keys.just_pressed(KeyCode::R) => {
+            next_state = Some(PlayerState::EnteringSeed {
+                seed: String::new(),
+                ascended: true,
+            });
+        }
+
+        PlayerState::EnteringSeed { seed, ascended } => {
+            if let Some(digit) = try_digit_keys(&keys) {
+                if seed.len() < 20 {
+                    seed.push(digit);
+                }
+            } else if keys.just_pressed(KeyCode::Back) {
+                seed.pop();
+            } else if keys.just_pressed(KeyCode::Escape) {
+                next_state = Some(if *ascended {
+                    PlayerState::Ascended
+                } else {
+                    PlayerState::Dead
+                });
+            } else if keys.just_pressed(KeyCode::Return) {
+                // an empty prompt just means "surprise me"
+                if seed.is_empty() || seed.parse::<u64>().is_ok() {
+                    run_seed.requested = seed.parse::<u64>().ok();
+                    procgen_events.send(ProcGenEvent::RestartWorld);
+                    turn_counter.0 = 0;
+                    next_state = Some(PlayerState::Help);
+                } else {
+                    seed.clear();
+                }
+            }
+        }
+
+        _ => {}
+    }
+
+    if let Some(state) = next_state {
+        *player_state = state;
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 #[allow(unused_assignments)]
@@ -102,7 +184,6 @@ pub fn character_controls(
     mut turn_counter: ResMut<TurnCounter>,
     mut turn_order: ResMut<TurnOrder>,
     grid: Res<Grid>,
-    map: Res<WorldData>,
     keys: Res<Input<KeyCode>>,
     mut commands: Commands,
     mut targeting: Query<(Entity, &mut Transform, &mut Targeting), Without<PlayerMarker>>,
@@ -111,7 +192,6 @@ pub fn character_controls(
             Entity,
             &WorldEntity,
             &mut Health,
-            &mut Character,
             &mut CarriedItems,
             &mut EquippedItems,
             &mut PendingActions,
@@ -123,11 +203,11 @@ pub fn character_controls(
         (Without<PlayerMarker>, Without<CarriedMarker>),
     >,
     carried_item_query: Query<&Item, With<CarriedMarker>>,
-    mut actions: EventWriter<ActionEvent>,
     mut history: ResMut<HistoryLog>,
-    mut depth: ResMut<LevelDepth>,
+    (mut depth, levels): (ResMut<LevelDepth>, Res<LevelCatalog>),
     mut currently_selected_item: ResMut<CurrentlySelectedItem>,
     mut player_state: ResMut<PlayerState>,
+    altars: Query<&WorldEntity, (With<Altar>, Without<PlayerMarker>)>,
 ) {
     if matches!(*player_state, PlayerState::Dead) && keys.just_pressed(KeyCode::Space) {
         *player_state = PlayerState::Help;
@@ -142,15 +222,8 @@ pub fn character_controls(
         }
     }
 
-    let Ok((
-        entity,
-        player_game_entity,
-        health,
-        character,
-        inventory,
-        equipped,
-        mut pending_actions,
-    )) = player_query.get_single_mut()
+    let Ok((entity, player_game_entity, health, inventory, equipped, mut pending_actions)) =
+        player_query.get_single_mut()
     else {
         //println!("#2");
         return;
@@ -163,6 +236,7 @@ pub fn character_controls(
     } else {
         match player_state.as_ref() {
             PlayerState::Shutdown => {}
+            PlayerState::EnteringSeed { .. } => {}
 
             PlayerState::Exiting => {
                 if keys.just_pressed(KeyCode::Return) || keys.just_pressed(KeyCode::Y) {
@@ -185,7 +259,7 @@ pub fn character_controls(
 
             PlayerState::Idle => {
                 if health.hitpoints.is_empty() {
-                    taken_action = Some(ActionEvent(a_wait()));
+                    taken_action = Some(ActionEvent(a_wait(entity)));
 
                     *player_state = PlayerState::Dead;
                     return;
@@ -194,12 +268,8 @@ pub fn character_controls(
                 let maybe_move = try_direction_keys(&keys);
                 if let Some(direction) = maybe_move {
                     if direction == IVec2::ZERO {
-                        taken_action = Some(ActionEvent(a_wait()));
-                    }
-                    if !map
-                        .solid
-                        .contains(&(player_game_entity.position + direction))
-                    {
+                        taken_action = Some(ActionEvent(a_wait(entity)));
+                    } else {
                         taken_action = Some(ActionEvent(a_move(entity, direction)));
                     }
                 } else if keys.just_pressed(KeyCode::Escape) {
@@ -218,7 +288,7 @@ pub fn character_controls(
                             items.iter().map(|i| i.0).collect::<Vec<_>>(),
                         )));
                     } else {
-                        history.add("Nothing to pick up");
+                        history.say("item.nothing_here", args());
                     }
                 } else if let Some(item_key) = try_item_keys(&keys) {
                     commands.add(SfxCommand { name: "ui_hover".to_string() });
@@ -228,9 +298,17 @@ pub fn character_controls(
                     *player_state = PlayerState::Help;
                 } else if keys.just_pressed(KeyCode::F) {
                     taken_action = Some(ActionEvent(a_focus(entity)));
-                } else if keys.just_pressed(KeyCode::M) && depth.0 < 5 {
-                    commands.add(SfxCommand { name: "ui_hover".to_string() });
-                    *player_state = PlayerState::SacrificeWarning;
+                } else if keys.just_pressed(KeyCode::M) && levels.has_next(depth.0) {
+                    // at an altar, an offering is made instead of the usual sacrifice, as long as
+                    // there's something to offer
+                    let at_altar =
+                        altars.iter().any(|altar| altar.position == player_game_entity.position);
+                    if at_altar && !inventory.0.is_empty() {
+                        taken_action = Some(ActionEvent(a_sacrifice(entity)));
+                    } else {
+                        commands.add(SfxCommand { name: "ui_hover".to_string() });
+                        *player_state = PlayerState::SacrificeWarning;
+                    }
                 }
             }
 
@@ -243,11 +321,11 @@ pub fn character_controls(
             }
 
             PlayerState::Sacrifice => {
-                history.add("You descend...");
-                history.add("---------------------------------");
+                history.say("descend", args());
+                history.say("descend.separator", args());
                 procgen_events.send(ProcGenEvent::NextLevel);
 
-                taken_action = Some(ActionEvent(a_descend()));
+                taken_action = Some(ActionEvent(a_descend(entity)));
                 depth.0 += 1;
                 *player_state = PlayerState::Descended;
             }
@@ -265,7 +343,7 @@ pub fn character_controls(
                     turn_counter.0 = 0;
                 }
 
-                taken_action = Some(ActionEvent(a_wait()));
+                taken_action = Some(ActionEvent(a_wait(entity)));
                 turn_order.pushback(100);
             }
 
@@ -409,7 +487,7 @@ pub fn character_controls(
 
             PlayerState::Reading(item) => {
                 if keys.just_pressed(KeyCode::Space) || keys.just_pressed(KeyCode::Escape) {
-                    taken_action = Some(ActionEvent(a_fortune(*item)));
+                    taken_action = Some(ActionEvent(a_fortune(entity, *item)));
                     *player_state = PlayerState::Idle;
                 }
             }
@@ -421,72 +499,72 @@ pub fn character_controls(
         }
     }
 
+    // paid for once the frame's done, unless it turns out it can't be done
     if let Some(action) = taken_action {
-        let cost = character.calculate_cost(action.0.get_affiliated_stat());
-        turn_order.pushback(cost);
-        actions.send(action);
+        commands.add(SubmitAction(action.0));
     }
 }
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct Achievements {
     pub octopus_mode: bool,
     pub messages: Vec<String>,
 }
 
-impl Default for Achievements {
-    fn default() -> Self {
-        Self { octopus_mode: false, messages: vec![
-            "(from the lost book of Agustin the Mage)\n\n [1/5] We start through LORE, like stories of old, ...".to_string(),
-            "(from the lost book of Agustin the Mage)\n\n [2/5]   ...through hardships up the knife, to the EDGE...".to_string(),
-            "(from the lost book of Agustin the Mage)\n\n [3/5]...to slice and fall, ourselves into PRISON cast...".to_string(),
-            "(from the lost book of Agustin the Mage)\n\n [4/5]   ...our REGALIA taken and thrown to the wolves...".to_string(),
-            "(from the lost book of Agustin the Mage)\n\n [5/5]...until we become DUST in someone else's cough.".to_string(),
-            "(from the Tome of Nhub)\n\nThrowing staffs is pretty inefficient...".to_string(),
-            "(from the Tome of Nhub)\n\nSacrifices all go to the HEALER...".to_string(),
-            "(from the Tome of Nhub)\n\nOf all the stats, only INT and WIL affect your sight...".to_string(),
-            "(from the Tome of Nhub)\n\nYour combat moves are faster if you have higher STR,\n and you walk faster if you have higher AGI!".to_string(),
-            "(from a tomb clad in leather)\n\n...be wary of sacrifices as they will undo ye.\n To travel, thou arth undone and then redone yet again.\n Thy vessel remade. Thy greatest strength turned against you.\n Nine they take.".to_string(),
-            "(from a quickly scribbled note)\n\nI see them now, the thaumaturg litanists of the Healer.\n I see them, and hear them too. I gave too much to stop,\n yet turn back I do as I understand the truth...".to_string(),
-            "(from a tomb clad in leather)\n\nat the ...scribble...demy of arts spiritual, they tell us\n to enscribe into our bones the chants of our enemies.\n From your bones to...".to_string(),
-            "(from the Tome of Nhub)\n\nUse FOCUS ('F' key) to move the effects of consumed bones deeper into\n your health bar, making them harder to remove.".to_string(),
-            "(from an empty page, a bodiless voice emanates)\n...THE BODY: the certain rejection of one's thaums\n is as inevitable as daylight after night. If you consume,\n it will spill out. So focus and consume deep.".to_string(),
-            "(from a crumbling piece of papyrus)\n\nFocus takes time. Focus means life. If you take other's bones, cast them not\n onto thyself without meaning and reason.\n Do so at the right moment, when thy bones dry out.".to_string(),
-            "(from the Tome of Nhub)\n\nIf you have high STR, your body will expel enchantments good or bad,\n pushing them from your deeper health points to the weaker\n ones on the right, and disappearing over time.".to_string(),
-            "(from the Tome of Nhub)\n\nIf your carpal tunnel is acting up, use SHIFT to run.\n It's not too precise but it gets you places.".to_string(),
-            "(from the Tome of Nhub)\n\nIf you have at least 8 STR, you will recover health over time.".to_string(),
-            "(from the Tome of Nhub)\n\nRaise WIS and ARC to start seeing auras - colors\n on items and monsters depicting their STRONGEST STAT.".to_string(),
-            "(a sad, crumpled, hacked up note)\n\nPlease disregard previous message.".to_string(),
-            "(a sad, crumpled, hacked up note)\n\nWizard needs food badly.".to_string(),
-            "(from the Tome of Nhub)\n\nThey don't see you if you don't see them, but they remember and they follow.".to_string(),
-            "(a disembodied voice escapes from a page of otherwise bland poetry)\n\nEr bones are not only good for sourcing one's thaums,\n but also fer cursing them with each other's thaums!\n Toss away and relish in their feeble state!".to_string(),
-            "(from the Tome of Nhub)\n\nEnchanters will curse you. Your health will show you colors.\n These are your stat colors and every 'v' symbol\n there means you have -1 of that stat. '^' means you have +1,\n and you can get that by focusing at the\n right spot and consuming bones".to_string(),
-        ] }
-    }
-}
-
-fn achievement_restart(mut procgen_events: EventReader<ProcGenEvent>, mut achievements: ResMut<Achievements>, mut rng: ResMut<Random>) {
+fn achievement_restart(
+    mut procgen_events: EventReader<ProcGenEvent>,
+    mut achievements: ResMut<Achievements>,
+    mut rng: ResMut<Random>,
+    log: Res<HistoryLog>,
+) {
     for procgen in procgen_events.read() {
         if *procgen == ProcGenEvent::RestartWorld {
             *achievements = Achievements::default();
-            achievements.messages = rng.shuffle(achievements.messages.clone());
+            // the writs come from the text catalog, so they're read in whatever language it's in
+            achievements.messages = rng.shuffle(log.text.writs.clone());
         }
     }
 }
 
+// lets the player know when they step up to an altar
+fn notice_altars(
+    mut outcomes: EventReader<ActionOutcome>,
+    player: Query<(), With<PlayerMarker>>,
+    altars: Query<&WorldEntity, With<Altar>>,
+    mut log: ResMut<HistoryLog>,
+) {
+    for outcome in outcomes.read() {
+        if let ActionOutcome::Moved { who, to, .. } | ActionOutcome::Teleported { who, to, .. } =
+            *outcome
+        {
+            if player.contains(who) && altars.iter().any(|altar| altar.position == to) {
+                log.say("altar.found", args());
+            }
+        }
+    }
+}
+
+// only looked at when the player puts something on
 fn octopus_tracker(
-    mut equipped_query: Query<(&mut TextureAtlasSprite, &EquippedItems), With<PlayerMarker>>,
+    mut outcomes: EventReader<ActionOutcome>,
+    mut equipped_query: Query<
+        (Entity, &mut TextureAtlasSprite, &EquippedItems),
+        With<PlayerMarker>,
+    >,
     items: Query<&Item>,
     mut log: ResMut<HistoryLog>,
     mut achievements: ResMut<Achievements>,
 ) {
-    if achievements.octopus_mode {
+    let Ok((player, mut sprite, equipped)) = equipped_query.get_single_mut() else {
         return;
-    }
+    };
 
-    let Ok((mut sprite, equipped)) = equipped_query.get_single_mut() else {
+    let player_equipped = outcomes.read().any(|outcome| {
+        matches!(outcome, ActionOutcome::ItemEquipped { who, .. } if *who == player)
+    });
+    if achievements.octopus_mode || !player_equipped {
         return;
-    };
+    }
 
     let count = equipped
         .0
@@ -501,7 +579,7 @@ fn octopus_tracker(
         .count();
 
     if count > 2 {
-        log.add("You wielded more than two weapons at once. Must be an octopus. Try doing a run without this for an achievement.");
+        log.say("item.octopus", args());
         achievements.octopus_mode = true;
         sprite.index = OCTOPUS.into();
     }
@@ -512,6 +590,12 @@ impl Plugin for SvarogPlayerPlugin {
     fn build(&self, bevy: &mut App) {
         bevy.init_resource::<PlayerState>();
         bevy.init_resource::<Achievements>();
+        bevy.add_systems(
+            Update,
+            seed_prompt_controls
+                .before(character_controls)
+                .run_if(in_state(GameStates::Game)),
+        );
         bevy.add_systems(
             Update,
             character_controls
@@ -519,7 +603,22 @@ impl Plugin for SvarogPlayerPlugin {
                 .before(CameraUpdateSystem)
                 .run_if(in_state(GameStates::Game)),
         );
-        bevy.add_systems(PostUpdate, (octopus_tracker, on_shutdown));
-        bevy.add_systems(Update, achievement_restart.run_if(on_event::<ProcGenEvent>()));
+        bevy.add_systems(PostUpdate, on_shutdown);
+        bevy.add_systems(
+            PostUpdate,
+            octopus_tracker.run_if(on_event::<ActionOutcome>()),
+        );
+        bevy.add_systems(
+            Update,
+            notice_altars
+                .after(handle_gameplay_action)
+                .run_if(on_event::<ActionOutcome>()),
+        );
+        bevy.add_systems(
+            Last,
+            achievement_restart
+                .after(generate_level)
+                .run_if(on_event::<ProcGenEvent>()),
+        );
     }
 }
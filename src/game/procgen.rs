@@ -1,5 +1,7 @@
 use bevy::{
+    ecs::system::SystemParam,
     prelude::*,
+    reflect::TypePath,
     render::view::RenderLayers,
     utils::{HashMap, HashSet},
 };
@@ -9,25 +11,26 @@ use bevy_mod_picking::{
     PickableBundle,
 };
 use doryen_fov::MapData;
+use serde::Deserialize;
 
 use crate::game::{
-    ai::PendingActions,
-    character::{Character, CharacterStat},
+    ai::{AIAgent, AIStrategy, PendingActions},
+    character::Character,
     feel::TweenSize,
     fov::Sight,
     grid::{WorldEntityBundle, WorldEntityKind},
     health::{Health, RecoveryCounter},
-    inventory::{CarriedItems, EquippedItems, ItemBuilder, ItemType},
+    inventory::{CarriedItems, EquippedItems, ItemBuilder, ItemCatalog},
     magic::{Focus, Magic},
-    mobs::{make_acolyte, make_bat, make_goblin, make_healer, make_orc, make_thaumaturge},
+    mobs::{spawn_monster, MonsterCatalog},
     sprite::{ChangePassability, ChangeSprite},
     sprites::*,
-    turns::{Energy, TurnOrderEntity, TurnTaker},
+    turns::TurnTaker,
     ui::ShowEntityDetails,
 };
 
 use super::{
-    feel::Random, fov::{on_new_fov_added, recalculate_fov, RecalculateFOVEvent}, grid::{Grid, Passability, WorldData, WorldEntity}, history::HistoryLog, turns::{TurnCounter, TurnOrder, TurnOrderProgressEvent}, DebugFlag, GameStates
+    feel::{Random, RunSeed}, fov::{on_new_fov_added, recalculate_fov, RecalculateFOVEvent}, grid::{Grid, Passability, WorldData, WorldEntity}, history::HistoryLog, turns::{TurnCounter, TurnOrder, TurnOrderProgressEvent}, DebugFlag, GameStates
 };
 
 #[derive(Event, PartialEq, Eq)]
@@ -45,9 +48,110 @@ pub struct MapRadius(pub i32);
 #[derive(Resource)]
 pub struct LevelDepth(pub u32, pub i32);
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct MobSpawn {
+    pub monster: String,
+    pub count: (i32, i32),
+    // overrides the monster's own strategy weights on this level
+    #[serde(default)]
+    pub strategy: Option<Vec<(AIStrategy, u32)>>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemSpawn {
+    pub template: String,
+    pub count: (i32, i32),
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LevelRecipe {
+    pub radius: i32,
+    pub obstructions: usize,
+    pub houses: usize,
+    pub forest_chance: u32,
+    pub forest: Vec<Tile>,
+    pub mobs: Vec<MobSpawn>,
+    pub items: Vec<ItemSpawn>,
+    #[serde(default)]
+    pub boss: Option<String>,
+    #[serde(default)]
+    pub altar: bool,
+}
+
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct LevelCatalog {
+    pub levels: Vec<LevelRecipe>,
+}
+
+impl LevelCatalog {
+    pub fn builtin() -> Self {
+        ron::from_str(include_str!("../../assets/hellth.levels.ron"))
+            .expect("Expecting the builtin level catalog to parse")
+    }
+
+    // depths start at 1
+    pub fn get(&self, depth: u32) -> Option<&LevelRecipe> {
+        depth
+            .checked_sub(1)
+            .and_then(|index| self.levels.get(index as usize))
+    }
+
+    pub fn has_next(&self, depth: u32) -> bool {
+        self.get(depth + 1).is_some()
+    }
+
+    pub fn is_boss_level(&self, depth: u32) -> bool {
+        self.get(depth)
+            .map(|level| level.boss.is_some())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for LevelCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+// everything the level generator reads from data files
+#[derive(SystemParam)]
+pub struct Catalogs<'w> {
+    pub monsters: Res<'w, MonsterCatalog>,
+    pub levels: Res<'w, LevelCatalog>,
+    pub items: Res<'w, ItemCatalog>,
+}
+
+// the shape and place of the level being generated
+#[derive(SystemParam)]
+pub struct LevelLayout<'w> {
+    pub grid: Res<'w, Grid>,
+    pub radius: ResMut<'w, MapRadius>,
+    pub depth: ResMut<'w, LevelDepth>,
+}
+
 #[derive(Component)]
 pub struct ClearLevel;
 
+// where an offering can be made to go down, see `SacrificeAction`
+#[derive(Component)]
+pub struct Altar;
+
+// restarting the world starts a new run, so it gets a new seed (or the one that was asked for)
+pub fn reseed_world(
+    mut procgen: EventReader<ProcGenEvent>,
+    mut rng: ResMut<Random>,
+    mut run_seed: ResMut<RunSeed>,
+) {
+    for proc in procgen.read() {
+        if proc == &ProcGenEvent::RestartWorld {
+            let seed = run_seed.requested.take().unwrap_or_else(|| rng.next_seed());
+            *rng = Random::seeded(seed);
+            run_seed.current = seed;
+            println!("Starting run with seed {}", seed);
+        }
+    }
+}
+
 #[allow(clippy::identity_op)]
 #[allow(clippy::too_many_arguments)]
 pub fn generate_level(
@@ -64,26 +168,19 @@ pub fn generate_level(
     mut turn_order_progress: EventWriter<TurnOrderProgressEvent>,
     mut log: ResMut<HistoryLog>,
     mut magic: ResMut<Magic>,
-    grid: Res<Grid>,
-    mut radius: ResMut<MapRadius>,
-    mut depth: ResMut<LevelDepth>,
+    catalogs: Catalogs,
+    layout: LevelLayout,
 ) {
+    let LevelLayout {
+        grid,
+        mut radius,
+        mut depth,
+    } = layout;
     let mut interiors: HashSet<IVec2> = HashSet::new();
 
     for proc in procgen.read() {
         let restart = proc == &ProcGenEvent::RestartWorld;
 
-        if !restart {
-            let mut r = radius.0;
-            r -= 50;
-
-            if r <= 50 {
-                r = 50;
-            }
-
-            radius.0 = r;
-        }
-
         if restart {
             magic.reset(&mut rng);
             log.clear();
@@ -103,6 +200,18 @@ pub fn generate_level(
             }
         }
 
+        let Some(recipe) = catalogs
+            .levels
+            .get(depth.0)
+            .or(catalogs.levels.levels.last())
+            .cloned()
+        else {
+            println!("No level recipes to generate depth {} from", depth.0);
+            continue;
+        };
+
+        radius.0 = recipe.radius;
+
         fn clear_grid(
             grid: &Res<Grid>,
             rng: &mut ResMut<Random>,
@@ -171,22 +280,12 @@ pub fn generate_level(
             size: IVec2,
             rng: &mut ResMut<Random>,
             grid: &Res<Grid>,
-            depth: &ResMut<LevelDepth>,
+            recipe: &LevelRecipe,
             map: &mut ResMut<WorldData>,
             okay: &mut HashSet<IVec2>,
             interiors: &mut HashSet<IVec2>,
         ) {
-            let mut forests = vec![EMPTY_FLOOR, FOREST1, FOREST2, FOREST3];
-            if depth.0 == 3 {
-                forests.extend(vec![ FOREST4, FOREST4, FOREST4, FOREST5 ]);
-            } else if depth.0 == 4 { 
-                forests.extend(vec![ FOREST4, FOREST4, FOREST5, FOREST6, FOREST7, FOREST7 ]);
-            } else if depth.0 == 5 {
-                forests.extend(vec![ FOREST4, FOREST7, FOREST8, FOREST7, FOREST8, FOREST7, FOREST8 ]);
-            }
-            let forest_tiles = Tiles::default()
-                .add_bunch(forests.as_slice())
-                .done();
+            let forest_tiles = Tiles::default().add_bunch(&recipe.forest).done();
 
             let ruin_tiles = Tiles::default()
                 .add_more(WALL1, 4)
@@ -197,7 +296,7 @@ pub fn generate_level(
                 let half = size / 2;
                 let middle = IVec2::new(rng.gen(-half.x..half.x), rng.gen(-half.y..half.y));
 
-                let (tiles, passability) = if rng.percent(45 - (depth.0 * 5).clamp(0, 25)) {
+                let (tiles, passability) = if rng.percent(100 - recipe.forest_chance.min(100)) {
                     (forest_tiles.as_slice(), Passability::SightBlocking)
                 } else {
                     (ruin_tiles.as_slice(), Passability::Blocking)
@@ -350,6 +449,9 @@ pub fn generate_level(
         map.memory.clear();
 
         turn_order.clear();
+        if restart {
+            turn_order.now = 0;
+        }
 
         let mut okay = clear_grid(
             &grid,
@@ -362,11 +464,11 @@ pub fn generate_level(
 
         make_obstructions(
             &mut commands,
-            20 + 3 * depth.0 as usize,
+            recipe.obstructions,
             size,
             &mut rng,
             &grid,
-            &depth,
+            &recipe,
             &mut map,
             &mut okay,
             &mut interiors,
@@ -374,7 +476,7 @@ pub fn generate_level(
 
         make_houses(
             &mut commands,
-            40 - 2 * depth.0 as usize,
+            recipe.houses,
             size,
             &mut rng,
             &grid,
@@ -383,15 +485,6 @@ pub fn generate_level(
             &mut interiors,
         );
 
-        let stats = [
-            CharacterStat::STR,
-            CharacterStat::ARC,
-            CharacterStat::INT,
-            CharacterStat::WIS,
-            CharacterStat::WIL,
-            CharacterStat::AGI,
-        ];
-
         let mut places_for_interior = rng.shuffle(interiors.clone().into_iter().collect());
 
         let mut places_for_spawning = rng.shuffle(
@@ -412,115 +505,22 @@ pub fn generate_level(
             }
         }
         
-        // add scrolls
-        for _ in 1..4 {
-            let mut builder = ItemBuilder::default()
-                .with_name("Arcane Writ")
-                .with_image(rng.from(&[SCROLL1, SCROLL2]))
-                .with_type(ItemType::Scroll);
-            
-            builder.create_at(
-                places_for_interior.pop().unwrap_or_default(),
-                &mut commands,
-                &grid,
-                &magic,
-            )
-        }
-
-        // add staffs
-        for _ in 1..(5 + depth.0) {
-            let mut builder = ItemBuilder::default()
-                .with_name("Staff")
-                .with_image(rng.from(&[STAFF1, STAFF2, STAFF3, STAFF4, STAFF5]))
-                .with_type(ItemType::Weapon);
-
-            builder = builder.with_stat(CharacterStat::ARC, 1);
-            builder = builder.with_stat(CharacterStat::WIS, 1);
-            for _ in 0..rng.gen(0..(2 + depth.0 as i32).clamp(2, 4)) {
-                let mut stat = rng.from(&stats);
-                let mut power = 0;
-                let mut attempt = 0;
-                while power == 0 || (stat == CharacterStat::ARC && stat == CharacterStat::WIS) {
-                    power = rng.gen((-1 - depth.0 as i32)..(5 + depth.0 as i32));
-                    stat = rng.from(&stats);
-                    attempt += 1;
-                    if attempt > 10 {
-                        break;
-                    }
-                }
-
-                builder = builder.with_stat(stat, power);
-            }
-
-            builder.create_at(
-                places_for_interior.pop().unwrap_or_default(),
-                &mut commands,
-                &grid,
-                &magic,
-            )
-        }
-
-        // add swords
-        for _ in 1..(depth.0 as i32 + rng.gen(0..(2 + depth.0 as i32).clamp(1, 4))) {
-            let mut builder = ItemBuilder::default()
-                .with_name("Sword")
-                .with_image(rng.from(&[SWORD1, SWORD2, SWORD3, SWORD4, SWORD5]))
-                .with_type(ItemType::Weapon);
-
-            builder = builder.with_stat(CharacterStat::STR, 2 + (depth.0 / 3) as i32);
-            for _ in 0..rng.gen(0..2) {
-                let mut stat = rng.from(&stats);
-                let mut power = 0;
-                let mut attempt = 0;
-                while power == 0 || stat == CharacterStat::STR {
-                    power = rng.gen((-1 - depth.0 as i32)..(5 + depth.0 as i32));
-                    stat = rng.from(&stats);
-                    attempt += 1;
-                    if attempt > 10 {
-                        break;
-                    }
-                }
-
-                builder = builder.with_stat(stat, power);
-            }
-
-            builder.create_at(
-                places_for_interior.pop().unwrap_or_default(),
-                &mut commands,
-                &grid,
-                &magic,
-            )
-        }
-
-        for _ in 1..(depth.0 as i32 + rng.gen(0..(2 + depth.0 as i32).clamp(2, 4))) {
-            let mut builder = ItemBuilder::default()
-                .with_name("Dagger")
-                .with_image(rng.from(&[DAGGER1, DAGGER2, DAGGER3, DAGGER4, DAGGER5]))
-                .with_type(ItemType::Weapon);
-
-            builder = builder.with_stat(CharacterStat::AGI, 2 + (depth.0 / 3) as i32);
-            for _ in 0..rng.gen(0..2) {
-                let mut stat = rng.from(&stats);
-                let mut power = 0;
-                let mut attempt = 0;
-                while power == 0 || stat == CharacterStat::AGI {
-                    power = rng.gen((-2 - depth.0 as i32)..(3 + depth.0 as i32));
-                    stat = rng.from(&stats);
-                    attempt += 1;
-                    if attempt > 10 {
-                        break;
-                    }
-                }
+        // add items
+        for spawn in &recipe.items {
+            for _ in 0..rng.roll(spawn.count) {
+                let Some(builder) =
+                    ItemBuilder::from_template(&catalogs.items, &spawn.template, depth.0, &mut rng)
+                else {
+                    break;
+                };
 
-                builder = builder.with_stat(stat, power);
+                builder.create_at(
+                    places_for_interior.pop().unwrap_or_default(),
+                    &mut commands,
+                    &grid,
+                    &magic,
+                )
             }
-
-            builder.create_at(
-                places_for_interior.pop().unwrap_or_default(),
-                &mut commands,
-                &grid,
-                &magic,
-            )
         }
 
         // add player
@@ -578,190 +578,63 @@ pub fn generate_level(
                     Sight(6),
                 ));
         } else {
-            turn_order.order.push(
-                TurnOrderEntity {
-                    entity: player.single(),
-                },
-                Energy(0),
-            );
+            turn_order.insert(player.single(), 0);
         }
 
         // add mobs
-
-        match depth.0 {
-            1 => {
-                for _ in 2..rng.gen(3..5) {
-                    let aggro = rng.percent(20u32);
-                    make_orc(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                        aggro,
-                    );
-                }
-
-                for _ in 3..rng.gen(6..10) {
-                    make_goblin(
-                        &mut commands,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 0..rng.gen(0..5) {
-                    make_bat(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-            }
-
-            2 => {
-                for _ in 2..rng.gen(2..5) {
-                    let aggro = rng.percent(20u32);
-                    make_orc(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                        aggro,
-                    );
-                }
-
-                for _ in 2..rng.gen(3..10) {
-                    make_goblin(
-                        &mut commands,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 0..rng.gen(2..6) {
-                    make_bat(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-            }
-
-            3 => {
-                for _ in 3..rng.gen(3..10) {
-                    make_acolyte(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 2..rng.gen(2..6) {
-                    make_goblin(
-                        &mut commands,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 0..rng.gen(0..3) {
-                    make_bat(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-            }
-
-            4 => {
-                for _ in 2..rng.gen(4..6) {
-                    make_acolyte(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 1..rng.gen(1..4) {
-                    make_thaumaturge(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 1..rng.gen(1..2) {
-                    let aggro = rng.percent(70u32);
-                    make_orc(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                        aggro
-                    );
-                }
-
-                for _ in 1..rng.gen(1..6) {
-                    make_goblin(
-                        &mut commands,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-            }
-
-            5 => {
-                for _ in 5..rng.gen(5..9) {
-                    make_bat(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 5..rng.gen(5..9) {
-                    make_goblin(
-                        &mut commands,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 1..rng.gen(3..5) {
-                    make_acolyte(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                for _ in 1..rng.gen(3..4) {
-                    make_thaumaturge(
-                        &mut commands,
-                        &mut rng,
-                        &grid,
-                        places_for_interior.pop().unwrap_or_default(),
-                    );
-                }
-
-                make_healer(
+        for mob in &recipe.mobs {
+            for _ in 0..rng.roll(mob.count) {
+                let strategy = mob
+                    .strategy
+                    .as_ref()
+                    .and_then(|weights| rng.weighted(weights));
+
+                let monster = spawn_monster(
                     &mut commands,
                     &mut rng,
                     &grid,
-                    depth.1 as i32,
+                    &catalogs.monsters,
+                    &mob.monster,
                     places_for_interior.pop().unwrap_or_default(),
+                    depth.1,
                 );
+
+                if let (Some(monster), Some(strategy)) = (monster, strategy) {
+                    commands.entity(monster).insert(AIAgent(strategy));
+                }
             }
+        }
 
-            _ => {}
+        if let Some(boss) = &recipe.boss {
+            spawn_monster(
+                &mut commands,
+                &mut rng,
+                &grid,
+                &catalogs.monsters,
+                boss,
+                places_for_interior.pop().unwrap_or_default(),
+                depth.1,
+            );
+        }
+
+        // last, so that it doesn't change where anything else ends up
+        if recipe.altar && catalogs.levels.has_next(depth.0) {
+            if let Some(place) = places_for_spawning.pop() {
+                commands.spawn((
+                    WorldEntityBundle::new(
+                        &grid,
+                        "Altar",
+                        place,
+                        ALTAR.into(),
+                        false,
+                        WorldEntityKind::Item,
+                        None,
+                    ),
+                    Altar,
+                    PickableBundle::default(),
+                    On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
+                ));
+            }
         }
 
         turn_order_progress.send(TurnOrderProgressEvent);
@@ -810,6 +683,8 @@ impl Plugin for SvarogProcgenPlugin {
             .insert_resource(LevelDepth(1, 0))
             .insert_resource(ClearColor(Color::BLACK))
             .insert_resource(Msaa::Off)
+            .init_resource::<MonsterCatalog>()
+            .init_resource::<LevelCatalog>()
             .add_systems(Update, on_new_fov_added.run_if(in_state(GameStates::Game)))
             .add_systems(
                 Update,
@@ -818,6 +693,11 @@ impl Plugin for SvarogProcgenPlugin {
                     .run_if(in_state(GameStates::Game)),
             )
             .add_systems(Update, (debug_radius, debug_procgen))
-            .add_systems(Last, generate_level.run_if(on_event::<ProcGenEvent>()));
+            .add_systems(
+                Last,
+                (reseed_world, generate_level)
+                    .chain()
+                    .run_if(on_event::<ProcGenEvent>()),
+            );
     }
 }
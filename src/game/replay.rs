@@ -0,0 +1,435 @@
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    actions::{handle_gameplay_action, AbstractAction, ActionEvent, ActionKind},
+    ai::{ai_agents_act, PendingActions},
+    explorer::explorer_mode_requested,
+    feel::RunSeed,
+    grid::WorldEntity,
+    history::{args, HistoryLog},
+    inventory::{CarriedItems, CarriedMarker, Item},
+    player::{character_controls, PlayerState},
+    procgen::{reseed_world, PlayerMarker, ProcGenEvent},
+    save::PendingLoad,
+    turns::{TurnCounter, TurnOrder},
+    GameStates,
+};
+
+pub const REPLAY_FILE: &str = "last_run.replay";
+
+// items are referred to by their slot in the player's inventory rather than by entity,
+// so a replay doesn't care about entities that only exist for show (like the throw target)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RecordedAction {
+    Move(IVec2),
+    Wait,
+    Pickup,
+    Focus,
+    Consume(usize),
+    Equip(usize),
+    Unequip(usize),
+    Drop(Vec<usize>),
+    Throw(usize, IVec2),
+    Fortune(usize),
+    Descend,
+    Sacrifice,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReplayHeader {
+    pub seed: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReplayEntry {
+    pub turn: u32,
+    pub action: RecordedAction,
+}
+
+pub fn slot_of(carried: &[Entity], item: Entity) -> Option<usize> {
+    carried.iter().position(|e| *e == item)
+}
+
+impl RecordedAction {
+    // only things the player could have chosen to do end up in a replay
+    pub fn from_kind(kind: ActionKind, player: Entity, carried: &[Entity]) -> Option<Self> {
+        let slot = |item: Entity| slot_of(carried, item);
+
+        match kind {
+            ActionKind::Move { entity, direction } if entity == player => {
+                Some(RecordedAction::Move(direction))
+            }
+            ActionKind::Wait { who } if who == player => Some(RecordedAction::Wait),
+            ActionKind::Pickup { who, .. } if who == player => Some(RecordedAction::Pickup),
+            ActionKind::Focus { who } if who == player => Some(RecordedAction::Focus),
+            ActionKind::Consume { who, what } if who == player => {
+                slot(what).map(RecordedAction::Consume)
+            }
+            ActionKind::Equip { who, what } if who == player => {
+                slot(what).map(RecordedAction::Equip)
+            }
+            ActionKind::Unequip { who, what } if who == player => {
+                slot(what).map(RecordedAction::Unequip)
+            }
+            ActionKind::Drop { who, what } if who == player => Some(RecordedAction::Drop(
+                what.into_iter().filter_map(slot).collect(),
+            )),
+            ActionKind::Throw { who, what, wher } if who == player => {
+                slot(what).map(|slot| RecordedAction::Throw(slot, wher))
+            }
+            ActionKind::Fortune { who, what } if who == player => {
+                slot(what).map(RecordedAction::Fortune)
+            }
+            ActionKind::Descend { who } if who == player => Some(RecordedAction::Descend),
+            ActionKind::Sacrifice { who } if who == player => Some(RecordedAction::Sacrifice),
+            _ => None,
+        }
+    }
+
+    pub fn to_action(
+        &self,
+        player: Entity,
+        carried: &[Entity],
+        items_here: Vec<Entity>,
+    ) -> Option<AbstractAction> {
+        let item = |slot: &usize| carried.get(*slot).copied();
+
+        let kind = match self {
+            RecordedAction::Move(direction) => ActionKind::Move {
+                entity: player,
+                direction: *direction,
+            },
+            RecordedAction::Wait => ActionKind::Wait { who: player },
+            RecordedAction::Pickup => ActionKind::Pickup {
+                who: player,
+                what: items_here,
+            },
+            RecordedAction::Focus => ActionKind::Focus { who: player },
+            RecordedAction::Consume(slot) => ActionKind::Consume {
+                who: player,
+                what: item(slot)?,
+            },
+            RecordedAction::Equip(slot) => ActionKind::Equip {
+                who: player,
+                what: item(slot)?,
+            },
+            RecordedAction::Unequip(slot) => ActionKind::Unequip {
+                who: player,
+                what: item(slot)?,
+            },
+            RecordedAction::Drop(slots) => ActionKind::Drop {
+                who: player,
+                what: slots.iter().filter_map(item).collect(),
+            },
+            RecordedAction::Throw(slot, target) => ActionKind::Throw {
+                who: player,
+                what: item(slot)?,
+                wher: *target,
+            },
+            RecordedAction::Fortune(slot) => ActionKind::Fortune {
+                who: player,
+                what: item(slot)?,
+            },
+            // descending has to go through the player state machine, see `replay_playback`
+            RecordedAction::Descend => return None,
+            RecordedAction::Sacrifice => ActionKind::Sacrifice { who: player },
+        };
+
+        Some(kind.into_action())
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    // off for a resumed run whose start isn't in the replay, until the next restart
+    pub recording: bool,
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub seed: u64,
+    pub entries: VecDeque<ReplayEntry>,
+    pub desynced: bool,
+}
+
+impl ReplayPlayback {
+    pub fn load(path: &str) -> Option<Self> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                println!("Couldn't read replay {}: {}", path, err);
+                return None;
+            }
+        };
+
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+        let header = ron::from_str::<ReplayHeader>(lines.next()?)
+            .map_err(|err| println!("Bad replay header in {}: {}", path, err))
+            .ok()?;
+
+        let mut entries = VecDeque::new();
+        for line in lines {
+            match ron::from_str::<ReplayEntry>(line) {
+                Ok(entry) => entries.push_back(entry),
+                Err(err) => {
+                    println!("Bad replay entry in {}: {}", path, err);
+                    return None;
+                }
+            }
+        }
+
+        Some(ReplayPlayback {
+            seed: header.seed,
+            entries,
+            desynced: false,
+        })
+    }
+}
+
+fn append_line(path: &Path, line: String, truncate: bool) {
+    let file = if truncate {
+        File::create(path)
+    } else {
+        OpenOptions::new().append(true).create(true).open(path)
+    };
+
+    match file {
+        Ok(mut file) => {
+            if let Err(err) = writeln!(file, "{}", line) {
+                println!("Couldn't write to replay {:?}: {}", path, err);
+            }
+        }
+        Err(err) => println!("Couldn't open replay {:?}: {}", path, err),
+    }
+}
+
+fn start_recording(
+    mut procgen: EventReader<ProcGenEvent>,
+    mut recorder: ResMut<ReplayRecorder>,
+    run_seed: Res<RunSeed>,
+    pending_load: Option<Res<PendingLoad>>,
+) {
+    // a run about to be resumed carries on with the replay it already has
+    if pending_load.is_some() {
+        procgen.clear();
+        return;
+    }
+
+    for proc in procgen.read() {
+        if proc == &ProcGenEvent::RestartWorld {
+            if let Ok(header) = ron::to_string(&ReplayHeader {
+                seed: run_seed.current,
+            }) {
+                append_line(&recorder.path, header, true);
+                recorder.recording = true;
+            }
+        }
+    }
+}
+
+fn record_player_actions(
+    mut actions: EventReader<ActionEvent>,
+    recorder: Res<ReplayRecorder>,
+    turn_counter: Res<TurnCounter>,
+    player_state: Res<PlayerState>,
+    player_query: Query<(Entity, &CarriedItems), With<PlayerMarker>>,
+) {
+    if !recorder.recording {
+        actions.clear();
+        return;
+    }
+
+    let Ok((player, carried)) = player_query.get_single() else {
+        return;
+    };
+
+    for action in actions.read() {
+        // the dead keep waiting every frame, none of that is interesting
+        if *player_state == PlayerState::Dead {
+            continue;
+        }
+
+        let Some(recorded) = RecordedAction::from_kind(action.0.kind(), player, &carried.0) else {
+            continue;
+        };
+
+        if let Ok(line) = ron::to_string(&ReplayEntry {
+            turn: turn_counter.0,
+            action: recorded,
+        }) {
+            append_line(&recorder.path, line, false);
+        }
+    }
+}
+
+// a resumed run keeps appending to its replay, as long as the replay is of that run. otherwise
+// there's nothing valid to record, and recording stops.
+pub fn resume_recording(world: &mut World, seed: u64) {
+    let Some(mut recorder) = world.get_resource_mut::<ReplayRecorder>() else {
+        return;
+    };
+
+    let header = std::fs::read_to_string(&recorder.path)
+        .ok()
+        .and_then(|contents| contents.lines().next().map(|line| line.to_string()))
+        .and_then(|line| ron::from_str::<ReplayHeader>(&line).ok());
+
+    if header.map(|header| header.seed) != Some(seed) {
+        println!("No replay of the resumed run in {:?}, not recording", recorder.path);
+        recorder.recording = false;
+    }
+}
+
+fn apply_replay_seed(playback: Res<ReplayPlayback>, mut run_seed: ResMut<RunSeed>) {
+    run_seed.requested = Some(playback.seed);
+}
+
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn replay_playback(
+    mut commands: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    turn_order: Res<TurnOrder>,
+    turn_counter: Res<TurnCounter>,
+    mut player_state: ResMut<PlayerState>,
+    mut player_query: Query<
+        (Entity, &WorldEntity, &CarriedItems, &mut PendingActions),
+        With<PlayerMarker>,
+    >,
+    free_items: Query<(Entity, &WorldEntity), (With<Item>, Without<CarriedMarker>)>,
+    mut log: ResMut<HistoryLog>,
+) {
+    if matches!(*player_state, PlayerState::Help | PlayerState::Descended) {
+        *player_state = PlayerState::Idle;
+    }
+
+    if *player_state != PlayerState::Idle {
+        return;
+    }
+
+    let Ok((player, player_world, carried, mut pending)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    if turn_order.peek() != Some(player) || !pending.0.is_empty() {
+        return;
+    }
+
+    let Some(entry) = playback.entries.pop_front() else {
+        log.say("replay.over", args());
+        commands.remove_resource::<ReplayPlayback>();
+        return;
+    };
+
+    if entry.turn != turn_counter.0 && !playback.desynced {
+        println!(
+            "Replay desynced: recorded on turn {}, replaying on turn {}",
+            entry.turn, turn_counter.0
+        );
+        log.say("replay.desynced", args());
+        playback.desynced = true;
+    }
+
+    if entry.action == RecordedAction::Descend {
+        *player_state = PlayerState::Sacrifice;
+        return;
+    }
+
+    let items_here = free_items
+        .iter()
+        .filter(|(_, w)| w.position == player_world.position)
+        .map(|(e, _)| e)
+        .collect::<Vec<_>>();
+
+    if let Some(action) = entry.action.to_action(player, &carried.0, items_here) {
+        pending.0.push_back(action);
+    } else if !playback.desynced {
+        println!("Replay desynced: couldn't rebuild {:?}", entry.action);
+        playback.desynced = true;
+    }
+}
+
+// `--replay <path>` plays a recorded run back, otherwise every run (short of explorer ones) gets
+// recorded to `REPLAY_FILE`
+pub struct SvarogReplayPlugin;
+
+impl Plugin for SvarogReplayPlugin {
+    fn build(&self, bevy: &mut App) {
+        let args = std::env::args().collect::<Vec<_>>();
+        let playback = args
+            .iter()
+            .position(|arg| arg == "--replay")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|path| ReplayPlayback::load(path));
+
+        if let Some(playback) = playback {
+            bevy.insert_resource(playback)
+                .add_systems(Startup, apply_replay_seed)
+                .add_systems(
+                    Update,
+                    replay_playback
+                        .before(character_controls)
+                        .run_if(resource_exists::<ReplayPlayback>())
+                        .run_if(in_state(GameStates::Game)),
+                );
+        } else if !explorer_mode_requested() {
+            // a run that went back in time can't be played back, so explorer runs aren't recorded
+            bevy.insert_resource(ReplayRecorder {
+                path: PathBuf::from(REPLAY_FILE),
+                recording: true,
+            })
+            .add_systems(
+                Last,
+                start_recording
+                    .after(reseed_world)
+                    .run_if(on_event::<ProcGenEvent>()),
+            )
+            .add_systems(
+                Update,
+                // before the monsters get to send anything of their own
+                record_player_actions
+                    .after(character_controls)
+                    .before(ai_agents_act)
+                    .before(handle_gameplay_action)
+                    .run_if(on_event::<ActionEvent>()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::game::actions::{
+        a_descend, a_fortune, a_wait,
+        tests::{clumsy, spawn_fighter, spawn_item, strong, test_world},
+    };
+
+    #[test]
+    fn replays_only_record_what_the_player_chose() {
+        let mut world = test_world();
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+        let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+        let writ = spawn_item(&mut world, player, vec![]);
+        let carried = vec![writ];
+
+        let record =
+            |action: AbstractAction| RecordedAction::from_kind(action.kind(), player, &carried);
+        assert_eq!(record(a_wait(player)), Some(RecordedAction::Wait));
+        assert_eq!(record(a_wait(goblin)), None);
+        assert_eq!(record(a_descend(goblin)), None);
+        assert_eq!(record(a_fortune(goblin, writ)), None);
+        assert_eq!(record(a_fortune(player, writ)), Some(RecordedAction::Fortune(0)));
+    }
+}
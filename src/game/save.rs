@@ -0,0 +1,686 @@
+use bevy::{prelude::*, render::view::RenderLayers, utils::HashMap};
+use bevy_mod_picking::{
+    events::{Click, Pointer},
+    prelude::On,
+    PickableBundle,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    actions::{AbstractAction, ActionKind},
+    ai::{
+        awareness::Awareness, factions::Faction, utility::UtilityProfile, AIAgent, AIStrategy,
+        PendingActions,
+    },
+    character::{Character, CharacterStat},
+    explorer::{explorer_mode_requested, ExplorerMode},
+    feel::{Random, RunSeed, TweenSize},
+    fov::{RecalculateFOVEvent, Sight},
+    grid::{Grid, Passability, WorldData, WorldEntity, WorldEntityBundle, WorldEntityColor, WorldEntityKind},
+    health::{Health, RecoveryCounter},
+    history::{args, HistoryLog},
+    inventory::{CarriedItems, CarriedMarker, CurrentlySelectedItem, EquippedItems, Item},
+    magic::{Focus, Magic, MagicAspect, StatShorthand},
+    mobs::{Mob, TheHealer},
+    navigation::Navigation,
+    outcomes::RunStatistics,
+    player::{on_shutdown, Achievements, PlayerState},
+    procgen::{Altar, ClearLevel, LevelDepth, MapRadius, PlayerMarker},
+    replay::resume_recording,
+    sprites::SELECTION,
+    status::StatusEffects,
+    turns::{add_entity_to_turn_queue, Speed, TurnCounter, TurnOrder, TurnTaker},
+    ui::ShowEntityDetails,
+    GameStates,
+};
+
+pub const SAVE_FILE: &str = "savegame.ron";
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedTile {
+    pub position: IVec2,
+    pub sprite: usize,
+    pub passability: Passability,
+    pub transparent: bool,
+    pub solid: bool,
+    pub remembered: bool,
+}
+
+// entity references (carried and equipped items, turn order, pending actions) are indices
+// into `SaveGame::entities`
+#[derive(Serialize, Deserialize)]
+pub struct SavedEntity {
+    pub world_entity: WorldEntity,
+    pub sprite: usize,
+    pub color: [f32; 4],
+    pub item: Option<Item>,
+    pub character: Option<Character>,
+    pub health: Option<Health>,
+    pub recovery: Option<u32>,
+    pub focus: Option<u32>,
+    pub sight: Option<u32>,
+    pub carried: Option<Vec<usize>>,
+    pub equipped: Option<Vec<usize>>,
+    pub ai: Option<AIStrategy>,
+    #[serde(default)]
+    pub awareness: Option<Awareness>,
+    #[serde(default)]
+    pub profile: Option<UtilityProfile>,
+    #[serde(default)]
+    pub faction: Option<Faction>,
+    #[serde(default)]
+    pub status: Option<StatusEffects>,
+    #[serde(default)]
+    pub speed: Option<Speed>,
+    pub pending: Option<Vec<ActionKind>>,
+    pub player: bool,
+    pub mob: bool,
+    pub healer: bool,
+    pub turn_taker: bool,
+    pub carried_marker: bool,
+    pub clear_level: bool,
+    #[serde(default)]
+    pub altar: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub seed: u64,
+    pub depth: (u32, i32),
+    pub radius: i32,
+    pub turns: u32,
+    pub colors: Vec<(CharacterStat, [f32; 4])>,
+    #[serde(default)]
+    pub aspects: Vec<(StatShorthand, MagicAspect)>,
+    // the aspects that already woke up this run
+    #[serde(default)]
+    pub awake: Vec<MagicAspect>,
+    pub log: Vec<String>,
+    pub octopus_mode: bool,
+    pub messages: Vec<String>,
+    pub tiles: Vec<SavedTile>,
+    pub entities: Vec<SavedEntity>,
+    // how long until each entity is up
+    pub energies: Vec<(usize, i32)>,
+    #[serde(default)]
+    pub time: u64,
+    // how long until each scheduled action happens
+    #[serde(default)]
+    pub scheduled: Vec<(u64, ActionKind)>,
+    #[serde(default)]
+    pub statistics: RunStatistics,
+    // where the random stream was, so rolls carry on the same way after loading
+    #[serde(default)]
+    pub rng: Option<Random>,
+}
+
+impl SaveGame {
+    pub fn capture(world: &mut World) -> Self {
+        let mut query = world.query_filtered::<Entity, With<WorldEntity>>();
+        let entities = query.iter(world).collect::<Vec<_>>();
+        let index = entities
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (*e, i))
+            .collect::<HashMap<_, _>>();
+
+        let remap = |list: &Vec<Entity>| {
+            list.iter()
+                .filter_map(|e| index.get(e).copied())
+                .collect::<Vec<_>>()
+        };
+
+        // anything that isn't saved (a despawned target, say) becomes a placeholder
+        let remap_action = |action: &AbstractAction| {
+            let mut kind = action.kind();
+            kind.map_entities(&mut |e| {
+                index
+                    .get(&e)
+                    .map(|i| Entity::from_raw(*i as u32))
+                    .unwrap_or(Entity::PLACEHOLDER)
+            });
+            kind
+        };
+
+        let saved_entities = entities
+            .iter()
+            .map(|e| {
+                let entity = world.entity(*e);
+                SavedEntity {
+                    world_entity: entity.get::<WorldEntity>().cloned().unwrap(),
+                    sprite: entity
+                        .get::<TextureAtlasSprite>()
+                        .map(|s| s.index)
+                        .unwrap_or_default(),
+                    color: entity
+                        .get::<WorldEntityColor>()
+                        .map(|c| c.color.as_rgba_f32())
+                        .unwrap_or([1.0; 4]),
+                    item: entity.get::<Item>().cloned(),
+                    character: entity.get::<Character>().cloned(),
+                    health: entity.get::<Health>().cloned(),
+                    recovery: entity.get::<RecoveryCounter>().map(|r| r.0),
+                    focus: entity.get::<Focus>().map(|f| f.0),
+                    sight: entity.get::<Sight>().map(|s| s.0),
+                    carried: entity.get::<CarriedItems>().map(|c| remap(&c.0)),
+                    equipped: entity.get::<EquippedItems>().map(|c| remap(&c.0)),
+                    ai: entity.get::<AIAgent>().map(|a| a.0),
+                    awareness: entity.get::<Awareness>().copied(),
+                    profile: entity.get::<UtilityProfile>().cloned(),
+                    faction: entity.get::<Faction>().copied(),
+                    status: entity.get::<StatusEffects>().cloned(),
+                    speed: entity.get::<Speed>().copied(),
+                    pending: entity
+                        .get::<PendingActions>()
+                        .map(|p| p.0.iter().map(remap_action).collect()),
+                    player: entity.contains::<PlayerMarker>(),
+                    mob: entity.contains::<Mob>(),
+                    healer: entity.contains::<TheHealer>(),
+                    turn_taker: entity.contains::<TurnTaker>(),
+                    carried_marker: entity.contains::<CarriedMarker>(),
+                    clear_level: entity.contains::<ClearLevel>(),
+                    altar: entity.contains::<Altar>(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let tiles = {
+            let grid = world.resource::<Grid>();
+            let map = world.resource::<WorldData>();
+            grid.entities
+                .iter()
+                .map(|(pos, e)| {
+                    let (x, y) = grid.norm(*pos);
+                    let tile = world.entity(*e);
+                    SavedTile {
+                        position: *pos,
+                        sprite: tile
+                            .get::<TextureAtlasSprite>()
+                            .map(|s| s.index)
+                            .unwrap_or_default(),
+                        passability: tile.get::<Passability>().copied().unwrap_or_default(),
+                        transparent: map.data.is_transparent(x, y),
+                        solid: map.solid.contains(pos),
+                        remembered: map.memory.contains(pos),
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // everyone's place in the queue is saved as how long until they're up
+        let turn_order = world.resource::<TurnOrder>();
+        let energies = turn_order
+            .order
+            .iter()
+            .filter_map(|(turn_entity, ready)| {
+                let delay = ready.at.saturating_sub(turn_order.now) as i32;
+                index.get(&turn_entity.entity).map(|i| (*i, delay))
+            })
+            .collect::<Vec<_>>();
+        let scheduled = turn_order
+            .scheduled
+            .iter()
+            .map(|scheduled| {
+                let delay = scheduled.at.saturating_sub(turn_order.now);
+                (delay, remap_action(&scheduled.action))
+            })
+            .collect::<Vec<_>>();
+        let time = turn_order.now;
+
+        let depth = world.resource::<LevelDepth>();
+        let achievements = world.resource::<Achievements>();
+
+        SaveGame {
+            seed: world.resource::<RunSeed>().current,
+            depth: (depth.0, depth.1),
+            radius: world.resource::<MapRadius>().0,
+            turns: world.resource::<TurnCounter>().0,
+            colors: world
+                .resource::<Magic>()
+                .color_bindings
+                .iter()
+                .map(|(stat, color)| (*stat, color.as_rgba_f32()))
+                .collect(),
+            aspects: world
+                .resource::<Magic>()
+                .aspects
+                .iter()
+                .map(|(stats, aspect)| (stats.clone(), *aspect))
+                .collect(),
+            awake: world.resource::<Magic>().awake.iter().copied().collect(),
+            log: world.resource::<HistoryLog>().lines.clone(),
+            octopus_mode: achievements.octopus_mode,
+            messages: achievements.messages.clone(),
+            tiles,
+            entities: saved_entities,
+            energies,
+            time,
+            scheduled,
+            statistics: world.resource::<RunStatistics>().clone(),
+            rng: Some(world.resource::<Random>().clone()),
+        }
+    }
+
+    pub fn restore(self, world: &mut World) {
+        // whatever was generated in the meantime makes way for the saved level
+        let mut query = world.query_filtered::<Entity, With<WorldEntity>>();
+        for e in query.iter(world).collect::<Vec<_>>() {
+            world.entity_mut(e).despawn_recursive();
+        }
+
+        {
+            let grid_entities = world
+                .resource::<Grid>()
+                .entities
+                .iter()
+                .map(|(pos, e)| (*pos, *e))
+                .collect::<HashMap<_, _>>();
+            let (grid_size_x, grid_size_y) = {
+                let grid = world.resource::<Grid>();
+                (grid.size.x, grid.size.y)
+            };
+
+            {
+                let mut map = world.resource_mut::<WorldData>();
+                map.solid.clear();
+                map.memory.clear();
+                map.blocking.clear();
+                for tile in &self.tiles {
+                    let x = (tile.position.x + grid_size_x / 2 + 1) as usize;
+                    let y = (tile.position.y + grid_size_y / 2 + 1) as usize;
+                    map.data.set_transparent(x, y, tile.transparent);
+                    if tile.solid {
+                        map.solid.insert(tile.position);
+                    }
+                    if tile.remembered {
+                        map.memory.insert(tile.position);
+                    }
+                }
+            }
+
+            for tile in &self.tiles {
+                let Some(e) = grid_entities.get(&tile.position) else {
+                    continue;
+                };
+
+                let mut tile_entity = world.entity_mut(*e);
+                if let Some(mut sprite) = tile_entity.get_mut::<TextureAtlasSprite>() {
+                    sprite.index = tile.sprite;
+                }
+                if let Some(mut passability) = tile_entity.get_mut::<Passability>() {
+                    *passability = tile.passability;
+                }
+            }
+
+            if let Some(mut navigation) = world.get_resource_mut::<Navigation>() {
+                navigation.invalidate();
+            }
+        }
+
+        let (atlas, transforms) = {
+            let grid = world.resource::<Grid>();
+            (
+                grid.atlas.clone_weak(),
+                self.entities
+                    .iter()
+                    .map(|e| grid.get_tile_position(e.world_entity.position))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let mut spawned = vec![];
+        for (saved, transform) in self.entities.iter().zip(transforms) {
+            let kind = if saved.player {
+                WorldEntityKind::Player
+            } else if saved.turn_taker {
+                WorldEntityKind::NPC
+            } else {
+                WorldEntityKind::Item
+            };
+
+            let mut bundle = WorldEntityBundle::new_raw(
+                transform,
+                atlas.clone_weak(),
+                &saved.world_entity.name,
+                saved.world_entity.position,
+                saved.sprite,
+                saved.world_entity.blocking,
+                kind,
+                Some(Color::rgba(saved.color[0], saved.color[1], saved.color[2], saved.color[3])),
+            );
+            bundle.entity = saved.world_entity.clone();
+            if saved.carried_marker {
+                bundle.sprite.visibility = Visibility::Hidden;
+            }
+
+            let child_atlas = atlas.clone_weak();
+            let mut entity = world.spawn(bundle);
+            entity.with_children(|f| {
+                f.spawn((
+                    SpriteSheetBundle {
+                        sprite: TextureAtlasSprite::new(0),
+                        texture_atlas: child_atlas.clone_weak(),
+                        transform: Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
+                        ..Default::default()
+                    },
+                    RenderLayers::layer(1),
+                ));
+
+                if saved.player {
+                    f.spawn((
+                        SpriteSheetBundle {
+                            sprite: TextureAtlasSprite::new(SELECTION.into()),
+                            texture_atlas: child_atlas.clone_weak(),
+                            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 1.0))
+                                .with_scale(Vec3::new(1.5, 1.5, 1.5)),
+                            ..Default::default()
+                        },
+                        RenderLayers::layer(1),
+                        TweenSize {
+                            baseline: 1.5,
+                            max: 0.25,
+                        },
+                    ));
+                }
+            });
+
+            entity.insert((
+                PickableBundle::default(),
+                On::<Pointer<Click>>::send_event::<ShowEntityDetails>(),
+            ));
+
+            if !saved.clear_level {
+                entity.remove::<ClearLevel>();
+            }
+            if let Some(item) = &saved.item {
+                entity.insert(item.clone());
+            }
+            if let Some(character) = &saved.character {
+                entity.insert(character.clone());
+            }
+            if let Some(health) = &saved.health {
+                entity.insert(health.clone());
+            }
+            if let Some(recovery) = saved.recovery {
+                entity.insert(RecoveryCounter(recovery));
+            }
+            if let Some(focus) = saved.focus {
+                entity.insert(Focus(focus));
+            }
+            if let Some(sight) = saved.sight {
+                entity.insert(Sight(sight));
+            }
+            if let Some(ai) = saved.ai {
+                entity.insert(AIAgent(ai));
+            }
+            if let Some(awareness) = saved.awareness {
+                entity.insert(awareness);
+            }
+            if let Some(profile) = saved.profile.clone() {
+                entity.insert(profile);
+            }
+            if let Some(faction) = saved.faction {
+                entity.insert(faction);
+            }
+            if let Some(status) = saved.status.clone() {
+                entity.insert(status);
+            }
+            if let Some(speed) = saved.speed {
+                entity.insert(speed);
+            }
+            if saved.player {
+                entity.insert(PlayerMarker);
+            }
+            if saved.mob {
+                entity.insert(Mob);
+            }
+            if saved.healer {
+                entity.insert(TheHealer);
+            }
+            if saved.altar {
+                entity.insert(Altar);
+            }
+            if saved.carried_marker {
+                entity.insert(CarriedMarker);
+            }
+            if saved.turn_taker {
+                entity.insert(TurnTaker);
+            }
+
+            spawned.push(entity.id());
+        }
+
+        // items can only be handed out once everyone exists
+        for (saved, e) in self.entities.iter().zip(spawned.iter()) {
+            let lookup = |list: &Vec<usize>| {
+                list.iter()
+                    .filter_map(|i| spawned.get(*i).copied())
+                    .collect::<Vec<_>>()
+            };
+
+            if let Some(carried) = &saved.carried {
+                world.entity_mut(*e).insert(CarriedItems(lookup(carried)));
+            }
+            if let Some(equipped) = &saved.equipped {
+                world.entity_mut(*e).insert(EquippedItems(lookup(equipped)));
+            }
+            if let Some(pending) = &saved.pending {
+                let actions = pending
+                    .iter()
+                    .cloned()
+                    .map(|mut kind| {
+                        kind.map_entities(&mut |i| {
+                            spawned
+                                .get(i.index() as usize)
+                                .copied()
+                                .unwrap_or(Entity::PLACEHOLDER)
+                        });
+                        kind.into_action()
+                    })
+                    .collect();
+                world.entity_mut(*e).insert(PendingActions(actions));
+            }
+        }
+
+        {
+            let mut turn_order = world.resource_mut::<TurnOrder>();
+            turn_order.clear();
+            turn_order.now = self.time;
+            for (i, delay) in &self.energies {
+                if let Some(entity) = spawned.get(*i) {
+                    turn_order.insert(*entity, (*delay).max(0) as u64);
+                }
+            }
+
+            for (delay, kind) in &self.scheduled {
+                let mut kind = kind.clone();
+                kind.map_entities(&mut |i| {
+                    spawned
+                        .get(i.index() as usize)
+                        .copied()
+                        .unwrap_or(Entity::PLACEHOLDER)
+                });
+                turn_order.schedule(*delay, kind.into_action());
+            }
+        }
+
+        world.resource_mut::<RunSeed>().current = self.seed;
+        if let Some(rng) = self.rng {
+            *world.resource_mut::<Random>() = rng;
+        }
+        *world.resource_mut::<LevelDepth>() = LevelDepth(self.depth.0, self.depth.1);
+        world.resource_mut::<MapRadius>().0 = self.radius;
+        world.resource_mut::<TurnCounter>().0 = self.turns;
+        world.resource_mut::<Magic>().color_bindings = self
+            .colors
+            .iter()
+            .map(|(stat, c)| (*stat, Color::rgba(c[0], c[1], c[2], c[3])))
+            .collect();
+        // saves from before aspects keep whatever this run rolled
+        if !self.aspects.is_empty() {
+            world.resource_mut::<Magic>().aspects = self.aspects.into_iter().collect();
+        }
+        world.resource_mut::<Magic>().awake = self.awake.into_iter().collect();
+        world.resource_mut::<HistoryLog>().lines = self.log;
+        {
+            let mut achievements = world.resource_mut::<Achievements>();
+            achievements.octopus_mode = self.octopus_mode;
+            achievements.messages = self.messages;
+        }
+        *world.resource_mut::<RunStatistics>() = self.statistics;
+        world.resource_mut::<CurrentlySelectedItem>().0 = None;
+        *world.resource_mut::<PlayerState>() = PlayerState::Idle;
+        world.send_event(RecalculateFOVEvent);
+    }
+}
+
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveGame);
+
+fn check_for_save(mut commands: Commands) {
+    // a replay has to start from its own seed, not from wherever we left off, and a real run
+    // doesn't carry on as practice
+    if std::env::args().any(|arg| arg == "--replay") || explorer_mode_requested() {
+        return;
+    }
+
+    let Ok(contents) = std::fs::read_to_string(SAVE_FILE) else {
+        return;
+    };
+
+    match ron::from_str::<SaveGame>(&contents) {
+        Ok(save) => commands.insert_resource(PendingLoad(save)),
+        Err(err) => println!("Couldn't read save {}: {}", SAVE_FILE, err),
+    }
+}
+
+// waits for the fresh level to exist, then swaps it out for the saved one
+fn load_pending_save(world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<PlayerMarker>>();
+    if query.iter(world).next().is_none() {
+        return;
+    }
+
+    let Some(PendingLoad(save)) = world.remove_resource::<PendingLoad>() else {
+        return;
+    };
+
+    let seed = save.seed;
+    save.restore(world);
+    resume_recording(world, seed);
+    world.resource_mut::<HistoryLog>().say("run.resumed", args());
+
+    // the roguelike way: a save only lives until it's loaded
+    if let Err(err) = std::fs::remove_file(SAVE_FILE) {
+        println!("Couldn't remove save {}: {}", SAVE_FILE, err);
+    }
+}
+
+fn save_on_exit(world: &mut World, mut saved: Local<bool>) {
+    if *saved || *world.resource::<PlayerState>() != PlayerState::Shutdown {
+        return;
+    }
+
+    // practice runs aren't something to resume as a real one
+    if world.contains_resource::<ExplorerMode>() {
+        return;
+    }
+
+    *saved = true;
+
+    let save = SaveGame::capture(world);
+    match ron::to_string(&save) {
+        Ok(contents) => {
+            if let Err(err) = std::fs::write(SAVE_FILE, contents) {
+                println!("Couldn't write save {}: {}", SAVE_FILE, err);
+            }
+        }
+        Err(err) => println!("Couldn't serialize save: {}", err),
+    }
+}
+
+pub struct SvarogSavePlugin;
+
+impl Plugin for SvarogSavePlugin {
+    fn build(&self, bevy: &mut App) {
+        bevy.add_systems(Startup, check_for_save)
+            .add_systems(
+                Update,
+                load_pending_save
+                    .after(add_entity_to_turn_queue)
+                    .run_if(resource_exists::<PendingLoad>())
+                    .run_if(in_state(GameStates::Game)),
+            )
+            .add_systems(PostUpdate, save_on_exit.before(on_shutdown));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::game::actions::{
+        a_death, a_hit, a_track,
+        tests::{clumsy, save_world, spawn_fighter, spawn_item, strong},
+    };
+
+    #[test]
+    fn saves_restore_the_run_as_it_was() {
+        let mut world = save_world();
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+        let goblin = spawn_fighter(&mut world, "Goblin", IVec2::X, clumsy(), 3);
+        let bones = spawn_item(&mut world, player, vec![(CharacterStat::STR, 1)]);
+        world.entity_mut(bones).insert(CarriedMarker);
+        let gone = world.spawn_empty().id();
+        world.entity_mut(goblin).insert(PendingActions(VecDeque::from([
+            a_hit(goblin, player),
+            a_track(goblin, gone),
+        ])));
+        {
+            let mut turn_order = world.resource_mut::<TurnOrder>();
+            turn_order.insert(player, 0);
+            turn_order.insert(goblin, 50);
+            turn_order.schedule(150, a_death(goblin));
+        }
+        world.resource_mut::<Magic>().awake.insert(MagicAspect::Dust);
+
+        let save = ron::to_string(&SaveGame::capture(&mut world)).unwrap();
+        let roll = world.resource_mut::<Random>().next_seed();
+        world.resource_mut::<Random>().next_seed();
+
+        world.resource_mut::<Magic>().awake.clear();
+        ron::from_str::<SaveGame>(&save).unwrap().restore(&mut world);
+        assert_eq!(world.resource_mut::<Random>().next_seed(), roll);
+        assert!(world.resource::<Magic>().awake.contains(&MagicAspect::Dust));
+
+        let mut players = world.query_filtered::<(Entity, &CarriedItems), With<PlayerMarker>>();
+        let (player, carried) = players.single(&world);
+        assert_eq!(carried.0.len(), 1);
+        let bones = carried.0[0];
+        assert_eq!(world.get::<Item>(bones).unwrap().equip_stat_changes, vec![(CharacterStat::STR, 1)]);
+        assert!(world.get::<CarriedMarker>(bones).is_some());
+
+        let mut monsters = world.query_filtered::<(Entity, &PendingActions), Without<PlayerMarker>>();
+        let (goblin, pending) = monsters.single(&world);
+        assert!(matches!(
+            pending.0[0].kind(),
+            ActionKind::Hit { attacker, target } if attacker == goblin && target == player
+        ));
+        // whoever wasn't saved can't be found again
+        assert!(matches!(
+            pending.0[1].kind(),
+            ActionKind::Track { who, target } if who == goblin && target == Entity::PLACEHOLDER
+        ));
+
+        let turn_order = world.resource::<TurnOrder>();
+        assert_eq!(turn_order.peek(), Some(player));
+        assert_eq!(turn_order.delay_of(goblin), Some(50));
+        assert_eq!(turn_order.scheduled.len(), 1);
+        assert_eq!(turn_order.scheduled[0].at, turn_order.now + 150);
+        assert!(matches!(
+            turn_order.scheduled[0].action.kind(),
+            ActionKind::Death { entity } if entity == goblin
+        ));
+    }
+}
@@ -1,2 +1,115 @@
-use bevy::{ecs::entity::Entity, math::IVec2};
+use bevy::{ecs::system::SystemState, prelude::*};
+use serde::{Deserialize, Serialize};
 
+use super::{
+    actions::{a_desintegrate, a_teleport, AbstractAction},
+    feel::Random,
+    grid::{WorldData, WorldEntity},
+    inventory::{CarriedMarker, Item},
+    status::{inflict, StatusEffect},
+};
+
+// how far a blink can take someone
+pub const BLINK_RANGE: i32 = 5;
+
+// how far from where it's cast disintegration reaches
+pub const DISINTEGRATE_RANGE: i32 = 2;
+
+// how many turns an affliction lasts
+pub const AFFLICT_TURNS: u32 = 4;
+
+// what an item does on top of its stats, see `Item::spell`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Spell {
+    // whoever it's cast on is thrown somewhere else nearby
+    Blink,
+    // everything lying on the floor around where it's cast turns to dust
+    Disintegrate,
+    // whoever it's cast on suffers the effect for a few turns
+    Afflict(StatusEffect),
+}
+
+impl Spell {
+    // `target` is whoever the spell is cast on, if anyone is standing at `at`
+    pub fn cast(
+        &self,
+        world: &mut World,
+        target: Option<Entity>,
+        at: IVec2,
+    ) -> Vec<AbstractAction> {
+        match self {
+            Spell::Blink => {
+                let Some(target) = target else {
+                    return vec![];
+                };
+
+                match blink_destination(world, target, None) {
+                    Some(wher) => vec![a_teleport(target, wher)],
+                    None => vec![],
+                }
+            }
+            Spell::Disintegrate => floor_items_near(world, at, DISINTEGRATE_RANGE)
+                .into_iter()
+                .map(a_desintegrate)
+                .collect(),
+            Spell::Afflict(effect) => {
+                if let Some(target) = target {
+                    inflict(world, target, *effect, AFFLICT_TURNS);
+                }
+                vec![]
+            }
+        }
+    }
+}
+
+// somewhere free within `BLINK_RANGE` of `who`: as far from `away_from` as possible if given,
+// anywhere at all otherwise
+pub fn blink_destination(
+    world: &mut World,
+    who: Entity,
+    away_from: Option<IVec2>,
+) -> Option<IVec2> {
+    let mut world_state =
+        SystemState::<(Res<WorldData>, ResMut<Random>, Query<&WorldEntity>)>::new(world);
+    let (world_data, mut rng, world_entities) = world_state.get_mut(world);
+
+    let from = world_entities.get(who).ok()?.position;
+    let mut free = vec![];
+    for x in -BLINK_RANGE..=BLINK_RANGE {
+        for y in -BLINK_RANGE..=BLINK_RANGE {
+            let place = from + IVec2::new(x, y);
+            if place != from
+                && !world_data.solid.contains(&place)
+                && !world_data.blocking.contains_key(&place)
+            {
+                free.push(place);
+            }
+        }
+    }
+
+    if free.is_empty() {
+        return None;
+    }
+
+    match away_from {
+        Some(away_from) => rng
+            .shuffle(free)
+            .into_iter()
+            .max_by_key(|place| place.distance_squared(away_from)),
+        None => Some(rng.from(&free)),
+    }
+}
+
+// items lying around within `range` of `at`, not the ones someone's carrying
+pub fn floor_items_near(world: &mut World, at: IVec2, range: i32) -> Vec<Entity> {
+    let mut query =
+        world.query_filtered::<(Entity, &WorldEntity), (With<Item>, Without<CarriedMarker>)>();
+    query
+        .iter(world)
+        .filter(|(_, world_entity)| {
+            let d = (world_entity.position - at).abs();
+            d.x.max(d.y) <= range
+        })
+        .map(|(entity, _)| entity)
+        .collect()
+}
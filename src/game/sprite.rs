@@ -1,6 +1,9 @@
 use bevy::{ecs::system::Command, math::IVec2, sprite::TextureAtlasSprite};
 
-use super::grid::{Grid, Passability};
+use super::{
+    grid::{Grid, Passability},
+    navigation::Navigation,
+};
 
 pub struct ChangeSprite {
     pub position: IVec2,
@@ -58,6 +61,11 @@ impl Command for ChangePassability {
             *passable = self.passable;
         }
 
+        // whatever was cached may now walk through walls
+        if let Some(mut navigation) = world.get_resource_mut::<Navigation>() {
+            navigation.invalidate();
+        }
+
         #[cfg(feature = "debug_mode")]
         {
             let mut query = world.query::<&mut TextureAtlasSprite>();
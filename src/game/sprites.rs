@@ -1,4 +1,6 @@
-#[derive(Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Tile(pub u32, pub u32);
 
 impl From<Tile> for usize {
@@ -32,6 +34,7 @@ pub const WALL5: Tile = Tile(18, 10);
 pub const WALL6: Tile = Tile(18, 11);
 
 pub const BONES: Tile = Tile(15, 0);
+pub const ALTAR: Tile = Tile(5, 21);
 
 pub const INTERIOR_FLOOR1: Tile = Tile(0, 16);
 pub const INTERIOR_FLOOR2: Tile = Tile(0, 17);
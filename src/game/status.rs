@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    fov::RecalculateFOVEvent,
+    grid::WorldEntity,
+    health::Health,
+    history::{args, HistoryLog},
+};
+
+// how far the blinded can see
+pub const BLINDED_SIGHT: usize = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusEffect {
+    // loses a hitpoint at the end of every turn
+    Burning,
+    // can't move, but can still fight back
+    Rooted,
+    // sees only what's right next to them
+    Blinded,
+    // everything takes half the time
+    Hasted,
+    // can't chant or yell
+    Silenced,
+    // loses a hitpoint at the end of every other turn
+    Bleeding,
+}
+
+const EFFECTS: [StatusEffect; 6] = [
+    StatusEffect::Burning,
+    StatusEffect::Rooted,
+    StatusEffect::Blinded,
+    StatusEffect::Hasted,
+    StatusEffect::Silenced,
+    StatusEffect::Bleeding,
+];
+
+impl StatusEffect {
+    // the name of the effect in the text catalog
+    pub fn key(&self) -> &'static str {
+        match self {
+            StatusEffect::Burning => "effect.burning",
+            StatusEffect::Rooted => "effect.rooted",
+            StatusEffect::Blinded => "effect.blinded",
+            StatusEffect::Hasted => "effect.hasted",
+            StatusEffect::Silenced => "effect.silenced",
+            StatusEffect::Bleeding => "effect.bleeding",
+        }
+    }
+}
+
+// the timed effects on a creature, with how many turns each has left. effects carried by a
+// hitpoint (see `HitPoint::status`) aren't in here, they last for as long as the hitpoint does.
+#[derive(Component, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct StatusEffects(pub Vec<(StatusEffect, u32)>);
+
+impl StatusEffects {
+    // the same effect twice doesn't stack, it just lasts as long as the longer of the two
+    pub fn add(&mut self, effect: StatusEffect, turns: u32) {
+        match self.0.iter_mut().find(|(e, _)| *e == effect) {
+            Some((_, left)) => *left = (*left).max(turns),
+            None => self.0.push((effect, turns)),
+        }
+    }
+
+    // counts down a turn, returning the effects that wore off
+    pub fn tick(&mut self) -> Vec<StatusEffect> {
+        for (_, left) in self.0.iter_mut() {
+            *left = left.saturating_sub(1);
+        }
+
+        let expired = self
+            .0
+            .iter()
+            .filter(|(_, left)| *left == 0)
+            .map(|(effect, _)| *effect)
+            .collect();
+        self.0.retain(|(_, left)| *left > 0);
+        expired
+    }
+}
+
+// every effect on a creature, timed or carried by one of its hitpoints
+pub fn active(effects: Option<&StatusEffects>, health: Option<&Health>) -> Vec<StatusEffect> {
+    EFFECTS
+        .into_iter()
+        .filter(|effect| suffers(effects, health, *effect))
+        .collect()
+}
+
+pub fn suffers(
+    effects: Option<&StatusEffects>,
+    health: Option<&Health>,
+    effect: StatusEffect,
+) -> bool {
+    let timed = effects.is_some_and(|effects| effects.0.iter().any(|(e, _)| *e == effect));
+    let carried =
+        health.is_some_and(|health| health.hitpoints.iter().any(|hp| hp.status == Some(effect)));
+    timed || carried
+}
+
+// for actions, which only have the world
+pub fn is_afflicted(world: &World, entity: Entity, effect: StatusEffect) -> bool {
+    suffers(world.get::<StatusEffects>(entity), world.get::<Health>(entity), effect)
+}
+
+pub fn inflict(world: &mut World, entity: Entity, effect: StatusEffect, turns: u32) {
+    let Some(mut target) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    match target.get_mut::<StatusEffects>() {
+        Some(mut effects) => effects.add(effect, turns),
+        None => {
+            target.insert(StatusEffects(vec![(effect, turns)]));
+        }
+    }
+
+    if effect == StatusEffect::Blinded {
+        world.send_event(RecalculateFOVEvent);
+    }
+
+    let Some(world_entity) = world.get::<WorldEntity>(entity).cloned() else {
+        return;
+    };
+
+    if let Some(mut log) = world.get_resource_mut::<HistoryLog>() {
+        let name = log.text(effect.key(), args());
+        log.say("effect.inflicted", args().subject(&world_entity).with("effect", name));
+    }
+}
@@ -1,80 +1,201 @@
 use bevy::prelude::*;
 use bevy_mod_imgui::ImguiContext;
 use priority_queue::PriorityQueue;
+use serde::{Deserialize, Serialize};
 
 use super::{
+    actions::{a_death, AbstractAction, ActionEvent},
     ai::{AIAgent, PendingActions},
     character::Character,
     feel::Random,
+    fov::RecalculateFOVEvent,
     grid::WorldEntity,
     health::{Health, HitPoint, RecoveryCounter},
-    history::HistoryLog,
+    history::{args, HistoryLog},
+    status::{suffers, StatusEffect, StatusEffects},
     DebugFlag,
 };
 
 #[derive(Component)]
 pub struct TurnTaker;
 
+// how much game time a turn is: what an action costs someone with a 3 in its stat
+pub const TURN: u64 = 100;
+
+// how fast someone goes about everything, in percent: at 200, actions take half the time
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Speed(pub u32);
+
+impl Default for Speed {
+    fn default() -> Self {
+        Speed(100)
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct TurnOrderEntity {
     pub entity: Entity,
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-pub struct Energy(pub i32);
+// when someone gets to act next. the earliest goes first, and of those ready at the same
+// time, whoever was scheduled first.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Ready {
+    pub at: u64,
+    pub seq: u64,
+}
 
-#[derive(Resource, Default)]
-pub struct TurnOrder {
-    pub order: PriorityQueue<TurnOrderEntity, Energy>,
-    pub should_ping: Option<Entity>,
+impl Ord for Ready {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.at.cmp(&self.at).then(other.seq.cmp(&self.seq))
+    }
 }
 
-#[derive(Event)]
-pub struct TurnOrderProgressEvent;
+impl PartialOrd for Ready {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// an action that happens on its own once the clock gets to `at`: a delayed spell going
+// off, the floor giving in, and so on
+#[derive(Debug)]
+pub struct ScheduledAction {
+    pub at: u64,
+    pub seq: u64,
+    pub action: AbstractAction,
+}
 
 #[derive(Event)]
-pub struct StartTurnEvent(pub Entity);
+pub struct TurnOrderProgressEvent;
 
 #[derive(Event)]
 pub struct EndTurnEvent;
 
+// how many turns the player has lived through
 #[derive(Resource, Default)]
 pub struct TurnCounter(pub u32);
 
+// who acts when. `now` is the game clock: it only ever moves forward, to whenever the next
+// one to act is ready, and every `TURN` of it that passes ends a turn.
+#[derive(Resource, Default)]
+pub struct TurnOrder {
+    pub order: PriorityQueue<TurnOrderEntity, Ready>,
+    pub scheduled: Vec<ScheduledAction>,
+    pub now: u64,
+    seq: u64,
+    due: Vec<AbstractAction>,
+    turns_ended: u32,
+}
+
 impl TurnOrder {
+    // forgets everyone and everything scheduled, but keeps the time
     pub fn clear(&mut self) {
         self.order.clear();
+        self.scheduled.clear();
+        self.due.clear();
     }
 
+    pub fn turn(&self) -> u64 {
+        self.now / TURN
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        self.seq += 1;
+        self.seq
+    }
+
+    // `entity` acts `delay` from now
+    pub fn insert(&mut self, entity: Entity, delay: u64) {
+        let ready = Ready {
+            at: self.now + delay,
+            seq: self.next_seq(),
+        };
+        self.order.push(TurnOrderEntity { entity }, ready);
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.order.get(&TurnOrderEntity { entity }).is_some()
+    }
+
+    // when `entity` acts next, counting from now
+    pub fn delay_of(&self, entity: Entity) -> Option<u64> {
+        self.order
+            .get_priority(&TurnOrderEntity { entity })
+            .map(|ready| ready.at.saturating_sub(self.now))
+    }
+
+    // whoever's up
     pub fn peek(&self) -> Option<Entity> {
         self.order.peek().map(|(a, _)| a.entity)
     }
 
-    pub fn pushback(&mut self, spend: i32) {
-        if let Some((top, energy)) = self.order.pop() {
-            self.order.push_decrease(top, Energy(energy.0 - spend));
-            self.should_ping = Some(self.order.peek().unwrap().0.entity);
+    // `action` happens `delay` from now, on its own
+    pub fn schedule(&mut self, delay: u64, action: AbstractAction) {
+        let at = self.now + delay;
+        let seq = self.next_seq();
+        self.scheduled.push(ScheduledAction { at, seq, action });
+    }
+
+    // whoever's up has acted, and will be up again once `cost` has passed. the clock stays
+    // put until the next `tick`.
+    pub fn pushback(&mut self, cost: i32) {
+        if let Some((top, _)) = self.order.pop() {
+            let ready = Ready {
+                at: self.now + cost.max(1) as u64,
+                seq: self.next_seq(),
+            };
+            self.order.push(top, ready);
         }
     }
 
-    pub fn is_turn_done(&self) -> bool {
-        self.order
-            .peek()
-            .map(|(_, Energy(energy))| *energy <= 0)
-            .unwrap_or(true)
-    }
-
-    pub fn restart_turn(&mut self) {
-        let entities = self
-            .order
-            .iter()
-            .map(|(a, b)| (*a, b.0))
-            .collect::<Vec<_>>();
-        for (entity, energy) in entities {
-            self.order
-                .change_priority(&entity, Energy((energy + 100).min(100)));
+    // moves the clock on to whoever's next, or to the next scheduled action if nobody's
+    // waiting to act. runs every frame as well as after every action, so whatever's scheduled
+    // for now happens even while nobody does anything.
+    pub fn tick(&mut self) {
+        self.scheduled.sort_by_key(|scheduled| (scheduled.at, scheduled.seq));
+        let next = match self.order.peek() {
+            Some((_, ready)) => ready.at,
+            None => match self.scheduled.first() {
+                Some(scheduled) => scheduled.at,
+                None => return,
+            },
+        };
+
+        // whatever's scheduled before then happens first, in order
+        let due = self.scheduled.iter().take_while(|s| s.at <= next).count();
+        self.due.extend(self.scheduled.drain(..due).map(|scheduled| scheduled.action));
+
+        if next > self.now {
+            self.turns_ended += (next / TURN - self.now / TURN) as u32;
+            self.now = next;
         }
     }
+
+    // the scheduled actions that came due, to be sent right after the action that paid for
+    // the time they needed
+    pub fn take_due(&mut self) -> Vec<AbstractAction> {
+        std::mem::take(&mut self.due)
+    }
+
+    pub fn take_turns_ended(&mut self) -> u32 {
+        std::mem::take(&mut self.turns_ended)
+    }
+}
+
+// what an action really costs someone, given how fast they are
+pub fn action_cost(
+    cost: i32,
+    speed: Option<&Speed>,
+    effects: Option<&StatusEffects>,
+    health: Option<&Health>,
+) -> i32 {
+    let mut speed = speed.copied().unwrap_or_default().0.max(1) as i32;
+    if suffers(effects, health, StatusEffect::Hasted) {
+        speed *= 2;
+    }
+
+    (cost * 100 / speed).max(1)
 }
 
 pub fn add_entity_to_turn_queue(
@@ -82,21 +203,25 @@ pub fn add_entity_to_turn_queue(
     mut turn_order: ResMut<TurnOrder>,
 ) {
     for entity in &turn_takers {
-        turn_order.order.push(TurnOrderEntity { entity }, Energy(0));
+        // restored entities come with their place in the queue already
+        if !turn_order.contains(entity) {
+            turn_order.insert(entity, 0);
+        }
     }
 }
 
 pub fn turn_order_progress(
     mut turn_order: ResMut<TurnOrder>,
-    mut start_turn_events: EventWriter<StartTurnEvent>,
+    mut actions: EventWriter<ActionEvent>,
     mut end_turn_events: EventWriter<EndTurnEvent>,
 ) {
-    if turn_order.peek().is_some() && turn_order.is_turn_done() {
-        turn_order.restart_turn();
+    turn_order.tick();
+    for action in turn_order.take_due() {
+        actions.send(ActionEvent(action));
+    }
+
+    for _ in 0..turn_order.take_turns_ended() {
         end_turn_events.send(EndTurnEvent);
-    } else if let Some(ping_target) = turn_order.should_ping {
-        start_turn_events.send(StartTurnEvent(ping_target));
-        turn_order.should_ping = None;
     }
 }
 
@@ -116,22 +241,70 @@ fn get_recovery_based_on_str(str: i32) -> u32 {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn on_turn_end(
     mut end_turn: EventReader<EndTurnEvent>,
     mut turn_counter: ResMut<TurnCounter>,
     mut health: Query<(
+        Entity,
         &mut Character,
         &mut Health,
         &mut RecoveryCounter,
         &WorldEntity,
+        Option<&mut StatusEffects>,
     )>,
     mut log: ResMut<HistoryLog>,
     mut rng: ResMut<Random>,
+    mut actions: EventWriter<ActionEvent>,
+    mut fov_events: EventWriter<RecalculateFOVEvent>,
 ) {
     for _ in end_turn.read() {
-        for (mut char, mut health, mut recovery, world_entity) in &mut health {
-            if world_entity.is_player && !health.hitpoints.is_empty() {
-                turn_counter.0 += 1;
+        // counted before anyone's looked at, so everyone bleeds on the same turns. only the
+        // living player's turns count, or the death screen's tally keeps climbing
+        let player_alive = health.iter().any(|(_, _, health, _, world_entity, _)| {
+            world_entity.is_player && !health.hitpoints.is_empty()
+        });
+        if player_alive {
+            turn_counter.0 += 1;
+        }
+
+        for (entity, mut char, mut health, mut recovery, world_entity, effects) in &mut health {
+            if health.hitpoints.is_empty() {
+                continue;
+            }
+
+            // fire and open wounds hurt, and timed effects wear off
+            let mut wounds = 0;
+            if suffers(effects.as_deref(), Some(&health), StatusEffect::Burning) {
+                wounds += 1;
+            }
+            if suffers(effects.as_deref(), Some(&health), StatusEffect::Bleeding)
+                && turn_counter.0.is_multiple_of(2)
+            {
+                wounds += 1;
+            }
+
+            if wounds > 0 {
+                for (stat, val) in health.normal_damage(wounds) {
+                    char[stat] += val;
+                }
+
+                log.say("effect.hurts", args().subject(world_entity).count(wounds as i64));
+                if health.hitpoints.is_empty() {
+                    actions.send(ActionEvent(a_death(entity)));
+                    continue;
+                }
+            }
+
+            if let Some(mut effects) = effects {
+                for effect in effects.tick() {
+                    if effect == StatusEffect::Blinded {
+                        fov_events.send(RecalculateFOVEvent);
+                    }
+
+                    let name = log.text(effect.key(), args());
+                    log.say("effect.ended", args().subject(world_entity).with("effect", name));
+                }
             }
 
             let turns_needed = get_recovery_based_on_str(char.strength);
@@ -142,15 +315,16 @@ fn on_turn_end(
                     health.hitpoints.push_front(HitPoint::default());
                     if let Some((stat, val)) = rightmost.stat {
                         char[stat] -= val;
-                        log.add(&format!(
-                            "You can feel your body force thaum out over time. {} {} is dispelled.",
-                            val,
-                            format!("{:?}", stat).to_uppercase(),
-                        ));
+                        log.say(
+                            "stat.dispelled",
+                            args()
+                                .with("amount", val)
+                                .with("stat", format!("{:?}", stat).to_uppercase()),
+                        );
                     }
 
                     if char.strength >= 8 && health.hitpoints.len() < health.size && rng.coin() {
-                        log.add("You can feel your wounds healing.");
+                        log.say("wounds.healing", args());
                         health.normal_heal(1);
                     }
                 }
@@ -176,7 +350,7 @@ fn debug_turn_order(
         .size([100.0, 300.0], imgui::Condition::FirstUseEver)
         .save_settings(true)
         .build(|| {
-            for (turn_taker, energy) in &turn_order.order {
+            for (turn_taker, ready) in &turn_order.order {
                 let Ok((entity, agent, plan)) = living.get(turn_taker.entity) else {
                     continue;
                 };
@@ -195,7 +369,10 @@ fn debug_turn_order(
 
                 ui.button(format!(
                     "{} ({}) {}{}",
-                    entity.name, energy.0, behaviour_name, plan
+                    entity.name,
+                    ready.at.saturating_sub(turn_order.now),
+                    behaviour_name,
+                    plan
                 ));
             }
         });
@@ -229,7 +406,6 @@ pub struct SvarogTurnPlugin;
 impl Plugin for SvarogTurnPlugin {
     fn build(&self, bevy: &mut App) {
         bevy.add_event::<TurnOrderProgressEvent>()
-            .add_event::<StartTurnEvent>()
             .init_resource::<TurnCounter>()
             .add_event::<EndTurnEvent>()
             .insert_resource(TurnOrder::default())
@@ -237,7 +413,108 @@ impl Plugin for SvarogTurnPlugin {
                 Update,
                 (add_entity_to_turn_queue, turn_order_progress).chain(),
             )
-            .add_systems(Update, on_turn_end.run_if(on_event::<EndTurnEvent>()))
-            .add_systems(Update, (debug_turn_order, debug_all_entities));
+            .add_systems(Update, on_turn_end.run_if(on_event::<EndTurnEvent>()));
+    }
+}
+
+pub struct SvarogTurnDebugPlugin;
+impl Plugin for SvarogTurnDebugPlugin {
+    fn build(&self, bevy: &mut App) {
+        bevy.add_systems(Update, (debug_turn_order, debug_all_entities));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::*;
+    use crate::game::actions::{
+        a_delay, a_wait,
+        tests::{run, spawn_fighter, strong, test_world},
+        ActionKind,
+    };
+
+    #[test]
+    fn the_dead_stop_counting_turns() {
+        let mut world = test_world();
+        world.init_resource::<TurnCounter>();
+        world.init_resource::<Events<EndTurnEvent>>();
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+        world.entity_mut(player).insert(RecoveryCounter(0));
+
+        world.send_event(EndTurnEvent);
+        world.run_system_once(on_turn_end);
+        assert_eq!(world.resource::<TurnCounter>().0, 1);
+
+        // the death screen keeps waiting, and that doesn't add to the tally
+        world.get_mut::<Health>(player).unwrap().hitpoints.clear();
+        for _ in 0..3 {
+            world.send_event(EndTurnEvent);
+            world.run_system_once(on_turn_end);
+        }
+        assert_eq!(world.resource::<TurnCounter>().0, 1);
+    }
+
+    #[test]
+    fn no_one_acts_twice_before_someone_faster_acts_once() {
+        let mut world = test_world();
+        let hasted = StatusEffects(vec![(StatusEffect::Hasted, 3)]);
+        assert_eq!(action_cost(100, Some(&Speed(200)), None, None), 50);
+        assert_eq!(action_cost(100, None, Some(&hasted), None), 50);
+
+        let slow = world.spawn_empty().id();
+        let fast = world.spawn_empty().id();
+        let mut turn_order = TurnOrder::default();
+        turn_order.insert(slow, 0);
+        turn_order.insert(fast, 0);
+
+        let mut acted = vec![];
+        for _ in 0..30 {
+            let who = turn_order.peek().unwrap();
+            let speed = if who == fast { Speed(150) } else { Speed(100) };
+            let before = turn_order.now;
+            acted.push(who);
+            turn_order.pushback(action_cost(TURN as i32, Some(&speed), None, None));
+            turn_order.tick();
+            assert!(turn_order.now >= before);
+        }
+
+        // in between any two of the slow one's turns, the fast one gets at least one
+        assert!(acted.windows(2).all(|pair| pair[0] != slow || pair[1] != slow));
+        assert!(acted.iter().filter(|who| **who == fast).count() > 15);
+    }
+
+    #[test]
+    fn delayed_actions_come_due_once_their_time_has_passed() {
+        let mut world = test_world();
+        let player = spawn_fighter(&mut world, "You", IVec2::ZERO, strong(), 10);
+        world.resource_mut::<TurnOrder>().insert(player, 0);
+
+        run(&mut world, a_delay(150, ActionKind::Death { entity: player }));
+
+        let mut turn_order = world.resource_mut::<TurnOrder>();
+        turn_order.pushback(TURN as i32);
+        turn_order.tick();
+        assert!(turn_order.take_due().is_empty());
+
+        turn_order.pushback(TURN as i32);
+        turn_order.tick();
+        let due = turn_order.take_due();
+        assert_eq!(due.len(), 1);
+        assert!(matches!(due[0].kind(), ActionKind::Death { entity } if entity == player));
+        assert_eq!(turn_order.turn(), 2);
+        assert_eq!(turn_order.take_turns_ended(), 2);
+
+        // the clock ticks on its own, so what's due now doesn't wait for anyone to act
+        turn_order.schedule(0, a_wait(player));
+        turn_order.tick();
+        assert_eq!(turn_order.take_due().len(), 1);
+
+        turn_order.clear();
+        turn_order.schedule(TURN, a_wait(player));
+        turn_order.tick();
+        assert_eq!(turn_order.take_due().len(), 1);
+        assert_eq!(turn_order.turn(), 3);
     }
 }
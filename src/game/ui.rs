@@ -8,14 +8,19 @@ use bevy_mod_picking::{
 use imgui::{DrawListMut, ImColor32, StyleColor};
 
 use super::{
+    ai::awareness::Awareness,
     character::{ Character, CharacterStat},
+    explorer::ExplorerMode,
+    feel::RunSeed,
     grid::{Grid, WorldData, WorldEntity, WorldEntityColor},
     health::Health,
-    history::HistoryLog,
+    history::{args, HistoryLog},
     inventory::{CarriedItems, CurrentlySelectedItem, EquippedItems, Item, ItemActions, ItemType},
     magic::Magic,
+    outcomes::RunStatistics,
     player::{Achievements, PlayerState},
     procgen::PlayerMarker,
+    status::{self, StatusEffects},
     turns::TurnCounter,
     DebugFlag, GameStates,
 };
@@ -23,11 +28,11 @@ use super::{
 use crate::game::magic::Focus;
 
 #[derive(Event, Debug)]
-pub struct ShowEntityDetails(Entity, f32);
+pub struct ShowEntityDetails(Entity);
 
 impl From<ListenerInput<Pointer<Click>>> for ShowEntityDetails {
     fn from(event: ListenerInput<Pointer<Click>>) -> Self {
-        ShowEntityDetails(event.target, event.hit.depth)
+        ShowEntityDetails(event.target)
     }
 }
 
@@ -41,10 +46,13 @@ pub fn on_show_details(
 ) {
     for detail in show_details.read() {
         if let Ok(world_entity) = world_entities.get(detail.0) {
-            log.add(&format!(
-                "Show Detail for {:?} at {:?}: {}",
-                detail, world_entity.position, world_entity.name
-            ));
+            log.say(
+                "details",
+                args()
+                    .with("target", format!("{:?}", detail))
+                    .with("position", format!("{:?}", world_entity.position))
+                    .with("subject", &world_entity.name),
+            );
         }
     }
 }
@@ -336,7 +344,7 @@ fn draw_hp_bar(
 
     if let Some(focus) = focus {
         if let Some(item) = item {
-            for (index, (stat, val)) in item.equip_stat_changes.iter().enumerate() {
+            for (index, (stat, _val)) in item.equip_stat_changes.iter().enumerate() {
                 let i = { (health.hitpoints.len() as i32 - 1 - index as i32 - focus as i32).clamp(0, 100) } as usize;
                 let p1 = [
                     p[0] + i as f32 * (width + padding) + offset.x,
@@ -477,8 +485,8 @@ fn show_inventory(
                             _ => None,
                         };
 
-                        if action_text.is_some() {
-                            ui.text(format!("    {}", action_text.unwrap()));
+                        if let Some(action_text) = action_text {
+                            ui.text(format!("    {}", action_text));
                         }
                     }
                 }
@@ -631,10 +639,10 @@ fn show_exit(
                 ui.text("Exit game?");
 
                 let [w, _] = ui.calc_text_size(
-                    "You will lose all progress if you quit. Do you want to proceed?",
+                    "Your run will be saved and waiting for you next time. Do you want to proceed?",
                 );
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 40.0]);
-                ui.text_wrapped("You will lose all progress if you quit. Do you want to proceed?");
+                ui.text_wrapped("Your run will be saved and waiting for you next time. Do you want to proceed?");
 
                 let [w, _] = ui.calc_text_size("[Enter/Y] Yes");
                 ui.set_cursor_pos([200.0 - w * 0.5, 150.0]);
@@ -653,6 +661,7 @@ fn show_descend_info(
     mut context: NonSendMut<ImguiContext>,
     player_state: Res<PlayerState>,
     depth: Res<LevelDepth>,
+    log: Res<HistoryLog>,
 ) {
 
     let ui = context.ui();
@@ -669,30 +678,58 @@ fn show_descend_info(
             .no_decoration()
             .bg_alpha(1.0)
             .build(|| {
-                let [w, _] = ui.calc_text_size("YOU HAVE DESCENDED.");
+                let title = log.text("screen.descended.title", args());
+                let [w, _] = ui.calc_text_size(&title);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 10.0]);
-                ui.text("YOU HAVE DESCENDED.");
+                ui.text(&title);
 
-                let text = format!(
-                    "You have descended into level {}. Stand proud, if you can stand.",
-                    depth.0
-                );
+                let text = log.text("screen.descended", args().with("depth", depth.0));
                 let [w, _] = ui.calc_text_size(&text);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 40.0]);
                 ui.text(&text);
 
-                let [w, _] = ui.calc_text_size("Press SPACE to continue.");
+                let press = log.text("help.continue", args());
+                let [w, _] = ui.calc_text_size(&press);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 80.0]);
-                ui.text("Press SPACE to continue.");
+                ui.text(&press);
             });
     }
 }
 
+fn describe_statistics(statistics: &RunStatistics, log: &HistoryLog) -> String {
+    log.text(
+        "screen.statistics",
+        args()
+            .with("steps", statistics.steps)
+            .with("dealt", statistics.damage_dealt)
+            .with("taken", statistics.damage_taken)
+            .with("fallen", statistics.fallen)
+            .with("consumed", statistics.items_consumed)
+            .with("resisted", statistics.spells_resisted),
+    )
+}
+
+// explorer runs say so right next to their seed
+fn describe_seed(run_seed: &RunSeed, explorer: Option<&ExplorerMode>, log: &HistoryLog) -> String {
+    let seed = log.text("screen.seed", args().with("seed", run_seed.current));
+    match explorer {
+        Some(explorer) => format!(
+            "{}. {}",
+            seed,
+            log.text("explorer.marked", args().count(explorer.rewinds))
+        ),
+        None => seed,
+    }
+}
+
 fn show_ascended_status(
     mut context: NonSendMut<ImguiContext>,
     player_state: Res<PlayerState>,
     turn_counter: Res<TurnCounter>,
     achievements: Res<Achievements>,
+    run_seed: Res<RunSeed>,
+    statistics: Res<RunStatistics>,
+    (explorer, log): (Option<Res<ExplorerMode>>, Res<HistoryLog>),
 ) {
     
     let ui = context.ui();
@@ -703,27 +740,38 @@ fn show_ascended_status(
         ui.window("CONGRATULATIONS")
             .position_pivot([0.5, 0.0])
             .position([w / 2.0, 100.0], imgui::Condition::Always)
-            .size([600.0, 110.0], imgui::Condition::Always)
+            .size([600.0, 170.0], imgui::Condition::Always)
             .resizable(false)
             .collapsible(false)
             .no_decoration()
             .bg_alpha(1.0)
             .build(|| {
-                let [w, _] = ui.calc_text_size("CONGRATULATIONS!");
+                let title = log.text("screen.victory.title", args());
+                let [w, _] = ui.calc_text_size(&title);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 10.0]);
-                ui.text("CONGRATULATIONS");
+                ui.text(&title);
 
-                let octo = achievements.octopus_mode;
-                let text = format!("You have beaten the Healer in {} turns{}.", turn_counter.0, 
-                    if octo { " and sacrificed your humanity along the way" } else { " without sacrificing your bipedal nature" });
+                let key = if achievements.octopus_mode { "screen.victory.octopus" } else { "screen.victory" };
+                let text = log.text(key, args().count(turn_counter.0));
 
                 let [w, _] = ui.calc_text_size(&text);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 40.0]);
                 ui.text(&text);
+
+                let seed = describe_seed(&run_seed, explorer.as_deref(), &log);
+                let [w, _] = ui.calc_text_size(&seed);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 70.0]);
+                ui.text(&seed);
+
+                let stats = describe_statistics(&statistics, &log);
+                let [w, _] = ui.calc_text_size(&stats);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 100.0]);
+                ui.text(&stats);
                 
-                let [w, _] = ui.calc_text_size("Press SPACE to restart.");
-                ui.set_cursor_pos([(600.0 - w) * 0.5, 80.0]);
-                ui.text("Press SPACE to restart.");
+                let restart = log.text("screen.restart", args());
+                let [w, _] = ui.calc_text_size(&restart);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 140.0]);
+                ui.text(&restart);
             });
     }
 }
@@ -733,6 +781,9 @@ fn show_dead_screen(
     player_state: Res<PlayerState>,
     depth: Res<LevelDepth>,
     turn_counter: Res<TurnCounter>,
+    run_seed: Res<RunSeed>,
+    statistics: Res<RunStatistics>,
+    (explorer, log): (Option<Res<ExplorerMode>>, Res<HistoryLog>),
 ) {
     let ui = context.ui();
 
@@ -742,28 +793,71 @@ fn show_dead_screen(
         ui.window("Ded")
             .position_pivot([0.5, 0.0])
             .position([w / 2.0, 100.0], imgui::Condition::Always)
-            .size([600.0, 110.0], imgui::Condition::Always)
+            .size([600.0, 170.0], imgui::Condition::Always)
             .resizable(false)
             .collapsible(false)
             .no_decoration()
             .bg_alpha(1.0)
             .build(|| {
-                let [w, _] = ui.calc_text_size("You died.");
+                let title = log.text("screen.dead.title", args());
+                let [w, _] = ui.calc_text_size(&title);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 10.0]);
-                ui.text("You died.");
+                ui.text(&title);
 
-                let text = format!(
-                    "You have been killed after {} turns on level {}.",
-                    turn_counter.0, depth.0
+                let text = log.text(
+                    "screen.dead",
+                    args().count(turn_counter.0).with("depth", depth.0),
                 );
 
                 let [w, _] = ui.calc_text_size(&text);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 40.0]);
                 ui.text(&text);
 
-                let [w, _] = ui.calc_text_size("Press SPACE to restart.");
-                ui.set_cursor_pos([(600.0 - w) * 0.5, 80.0]);
-                ui.text("Press SPACE to restart.");
+                let seed = describe_seed(&run_seed, explorer.as_deref(), &log);
+                let [w, _] = ui.calc_text_size(&seed);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 70.0]);
+                ui.text(&seed);
+
+                let stats = describe_statistics(&statistics, &log);
+                let [w, _] = ui.calc_text_size(&stats);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 100.0]);
+                ui.text(&stats);
+
+                let restart = log.text("screen.restart", args());
+                let [w, _] = ui.calc_text_size(&restart);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 140.0]);
+                ui.text(&restart);
+            });
+    }
+}
+
+fn show_seed_prompt(mut context: NonSendMut<ImguiContext>, player_state: Res<PlayerState>) {
+    let ui = context.ui();
+
+    if let PlayerState::EnteringSeed { seed, .. } = player_state.as_ref() {
+        let [w, _] = ui.io().display_size;
+
+        ui.window("Seed")
+            .position_pivot([0.5, 0.0])
+            .position([w / 2.0, 100.0], imgui::Condition::Always)
+            .size([600.0, 140.0], imgui::Condition::Always)
+            .resizable(false)
+            .collapsible(false)
+            .no_decoration()
+            .bg_alpha(1.0)
+            .build(|| {
+                let [w, _] = ui.calc_text_size("Restart from a seed");
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 10.0]);
+                ui.text("Restart from a seed");
+
+                let text = format!("> {}_", seed);
+                let [w, _] = ui.calc_text_size(&text);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 50.0]);
+                ui.text(&text);
+
+                let [w, _] = ui.calc_text_size("[Enter] Restart (empty for a random seed)    [Escape] Back");
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 110.0]);
+                ui.text("[Enter] Restart (empty for a random seed)    [Escape] Back");
             });
     }
 }
@@ -771,7 +865,7 @@ fn show_dead_screen(
 fn show_writ(mut context: NonSendMut<ImguiContext>, player_state: Res<PlayerState>, ach: Res<Achievements>) {
     let ui = context.ui();
 
-    if let PlayerState::Reading(writ) = *player_state {
+    if let PlayerState::Reading(_) = *player_state {
         if let Some(message) = ach.messages.last() {
             let [w, _] = ui.io().display_size;
 
@@ -797,7 +891,7 @@ fn show_writ(mut context: NonSendMut<ImguiContext>, player_state: Res<PlayerStat
     }
 }
 
-fn show_help(mut context: NonSendMut<ImguiContext>, player_state: Res<PlayerState>) {
+fn show_help(mut context: NonSendMut<ImguiContext>, player_state: Res<PlayerState>, log: Res<HistoryLog>) {
     let ui = context.ui();
 
     if matches!(*player_state, PlayerState::Help) {
@@ -806,39 +900,32 @@ fn show_help(mut context: NonSendMut<ImguiContext>, player_state: Res<PlayerStat
         ui.window("Tip")
             .position_pivot([0.5, 0.0])
             .position([w / 2.0, 100.0], imgui::Condition::Always)
-            .size([600.0, 370.0], imgui::Condition::Always)
+            .size([600.0, 390.0], imgui::Condition::Always)
             .resizable(false)
             .collapsible(false)
             .no_decoration()
             .bg_alpha(1.0)
             .build(|| {
-                let [w, _] = ui.calc_text_size("HOW TO");
+                let title = log.text("help.title", args());
+                let [w, _] = ui.calc_text_size(&title);
                 ui.set_cursor_pos([(600.0 - w) * 0.5, 10.0]);
-                ui.text("HOW TO");
+                ui.text(&title);
 
-                ui.text("Hark thee!");
+                ui.text(log.text("help.hark", args()));
                 ui.spacing();
-                let mut message = vec!["You are the latest in a long line of acolytes sent to venture into the Ruins of the World in the hopes of slaying the Healer.".to_string()];                
-                message.push("Going down doesn't require only time, but sacrifice. If at least one of your stats isn't at 9, you will lose some health to the Healer...".to_string());
-                message.push("You choose when to descend. Stats are enscribed into your health bar, watch it closely. Don't waste items.".to_string());
-                ui.text_wrapped(message.join(" "));
-                ui.text_wrapped("Staircases going down don't exist. Consume. Grow. Sacrifice. Find a way.");
+                ui.text_wrapped(log.text("help.intro", args()));
+                ui.text_wrapped(log.text("help.motto", args()));
                 ui.separator();
-                ui.text_wrapped("Help (this screen): H");
-                ui.text_wrapped("Movement: ASDW + QEZC (diagonal)");
-                ui.text_wrapped("Make Sacrifice (attempt to descend): M");
-                ui.text_wrapped("Focus Thaumaturgy (affect other health points with consumed bones): F");
-                ui.text_wrapped("Wait Turn: X");
-                ui.text_wrapped("Cancel: Escape");
-                ui.text_wrapped("Pickup: Space or G");
-                ui.text_wrapped("Items: 1-9 to start interaction");
-                ui.text_wrapped("Volume: -/+");
+                for line in log.text("help.controls", args()).lines() {
+                    ui.text_wrapped(line);
+                }
                 ui.separator();
                 ui.spacing();
 
-                let [w, _] = ui.calc_text_size("Press SPACE to continue.");
-                ui.set_cursor_pos([(600.0 - w) * 0.5, 350.0]);
-                ui.text("Press SPACE to continue.");
+                let press = log.text("help.continue", args());
+                let [w, _] = ui.calc_text_size(&press);
+                ui.set_cursor_pos([(600.0 - w) * 0.5, 370.0]);
+                ui.text(&press);
             });
     }
 }
@@ -857,7 +944,7 @@ fn show_log(mut context: NonSendMut<ImguiContext>, log: Res<HistoryLog>) {
         .build(|| {
             ui.text("History");
             ui.separator();
-            for line in log.0.iter() {
+            for line in log.lines.iter() {
                 let c = ui.push_style_color(
                     StyleColor::Text,
                     [207. / 255., 198. / 255., 184. / 255., 1.0],
@@ -870,15 +957,17 @@ fn show_log(mut context: NonSendMut<ImguiContext>, log: Res<HistoryLog>) {
 }
 
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 fn show_status_for_world_entities(
-    mut player_entity: Query<(&WorldEntity, &mut Character, &Health, &CarriedItems, &Focus), With<PlayerMarker>>,
-    world_entities: Query<(&WorldEntity, &Character, &Health), Without<PlayerMarker>>,
+    mut player_entity: Query<(&WorldEntity, &mut Character, &Health, &CarriedItems, &Focus, Option<&StatusEffects>), With<PlayerMarker>>,
+    world_entities: Query<(&WorldEntity, &Character, &Health, Option<&Awareness>, Option<&StatusEffects>), Without<PlayerMarker>>,
     grid: Option<Res<Grid>>,
     world: Res<WorldData>,
     items: Query<&Item>,
     health_settings: Res<CharacterSettings>,
     magic: Res<Magic>,
     player_state: Res<PlayerState>,
+    log: Res<HistoryLog>,
     mut context: NonSendMut<ImguiContext>,
 ) {
     let Some(grid) = grid else {
@@ -889,7 +978,7 @@ fn show_status_for_world_entities(
 
     let [width, _height] = ui.io().display_size;
 
-    let Ok((player, mut player_char, player_health, inventory, focus)) = player_entity.get_single_mut() else {
+    let Ok((player, mut player_char, player_health, inventory, focus, player_effects)) = player_entity.get_single_mut() else {
         return;
     };
 
@@ -904,6 +993,7 @@ fn show_status_for_world_entities(
         .build(|| {
             let draw = ui.get_window_draw_list();
             ui.text(&player.name);
+            show_effects(ui, &log, player_effects, player_health);
             ui.separator();
             let p: Vec2 = ui.window_pos().into();
 
@@ -936,7 +1026,7 @@ fn show_status_for_world_entities(
         });
 
     let mut window_y = 10.0f32;
-    for (other_entity, other_char, other_health) in &world_entities {
+    for (other_entity, other_char, other_health, awareness, effects) in &world_entities {
         let (x, y) = grid.norm(other_entity.position);
         if world.data.is_in_fov(x, y) {
             ui.window(&format!("{}{}", other_entity.name, window_y))
@@ -949,6 +1039,17 @@ fn show_status_for_world_entities(
                 .build(|| {
                     let draw = ui.get_window_draw_list();
                     ui.text(&other_entity.name);
+                    if let Some(awareness) = awareness {
+                        let (status, color) = match awareness {
+                            _ if awareness.has_noticed() => ("status.noticed", [1.0, 0.3, 0.3, 1.0]),
+                            Awareness::Suspicious { .. } => ("status.suspicious", [1.0, 0.8, 0.3, 1.0]),
+                            _ => ("status.unaware", [0.6, 0.6, 0.6, 1.0]),
+                        };
+
+                        ui.same_line();
+                        ui.text_colored(color, format!("({})", log.text(status, args())));
+                    }
+                    show_effects(ui, &log, effects, other_health);
                     let p: Vec2 = ui.window_pos().into();
 
                     draw_hp_bar(&draw, p, other_health, &magic, &health_settings, None, None);
@@ -968,7 +1069,19 @@ fn show_status_for_world_entities(
     }
 }
 
-pub fn show_progress_status(mut context: NonSendMut<ImguiContext>, level_depth: Res<LevelDepth>, turn_counter: Res<TurnCounter>) {
+fn show_effects(ui: &imgui::Ui, log: &HistoryLog, effects: Option<&StatusEffects>, health: &Health) {
+    let names = status::active(effects, Some(health))
+        .iter()
+        .map(|effect| log.text(effect.key(), args()))
+        .collect::<Vec<_>>();
+
+    if !names.is_empty() {
+        ui.same_line();
+        ui.text_colored([1.0, 0.5, 0.2, 1.0], format!("[{}]", names.join(", ")));
+    }
+}
+
+pub fn show_progress_status(mut context: NonSendMut<ImguiContext>, level_depth: Res<LevelDepth>, turn_counter: Res<TurnCounter>, run_seed: Res<RunSeed>) {
     let ui = context.ui();
 
     let [width, height] = ui.io().display_size;
@@ -976,7 +1089,7 @@ pub fn show_progress_status(mut context: NonSendMut<ImguiContext>, level_depth:
     ui.window("PROGRESS")
         .position_pivot([1.0, 1.0])
         .position([width - 20.0, height - 20.0], imgui::Condition::Always)
-        .size([100.0, 80.0], imgui::Condition::Always)
+        .size([200.0, 100.0], imgui::Condition::Always)
         .resizable(false)
         .collapsible(false)
         .no_decoration()
@@ -984,6 +1097,7 @@ pub fn show_progress_status(mut context: NonSendMut<ImguiContext>, level_depth:
         .build(|| {
             ui.text(format!("Depth: {}", level_depth.0));
             ui.text(format!("Turns: {}", turn_counter.0));
+            ui.text(format!("Seed: {}", run_seed.current));
             ui.separator();
             ui.text("H: Help");
         });
@@ -1004,6 +1118,7 @@ impl Plugin for SvarogUIPlugin {
                 show_sacrifice_warning,
                 show_descend_info,
                 show_dead_screen,
+                show_seed_prompt,
                 show_ascended_status,
                 show_exit,
                 draw_health_settings,